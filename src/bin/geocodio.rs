@@ -0,0 +1,281 @@
+//! Command-line interface to the Geocodio API, enabled with the `cli` feature.
+//!
+//! ```text
+//! geocodio geocode "1500 Sugar Bowl Dr, New Orleans, LA 70112"
+//! geocodio reverse 40.81352,-74.074333 --format csv
+//! geocodio reverse 40.81352,-74.074333 --max-distance-km 0.5 --rooftop-only
+//! geocodio batch input.csv --address-column address --fields timezone,cd -o out.csv
+//! geocodio batch input.csv --line-1-column street --city-column city --state-column state --postal-code-column zip
+//! ```
+
+use std::{fs::File, io::{self, BufWriter, Write}, path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use geocodio_lib_rust::{
+    csv::{address_record, geocode_csv, read_addresses, result_headers, AddressColumns, ColumnMapping, CsvOptions, CsvRows},
+    errors::Error,
+    request::{address::{AddressParams, Coordinates}, options::GeocodeOptions},
+    response::address::Address,
+    GeocodioProxy,
+};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(name = "geocodio", version, about = "Geocode addresses and coordinates with the Geocodio API")]
+struct Cli {
+    /// Geocodio API key. Defaults to GEOCODIO_API_KEY from the environment or a .env file.
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
+    /// Base URL of the Geocodio API.
+    #[arg(long, global = true, hide = true)]
+    base_url: Option<reqwest::Url>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Geocode a single address.
+    Geocode {
+        address: String,
+        #[command(flatten)]
+        query: QueryArgs,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+    },
+    /// Reverse geocode a "lat,lng" pair.
+    Reverse {
+        #[arg(allow_hyphen_values = true)]
+        coordinates: String,
+        #[command(flatten)]
+        query: QueryArgs,
+        /// Drop results further than this many kilometers away.
+        #[arg(long)]
+        max_distance_km: Option<f64>,
+        /// Only return rooftop and point results.
+        #[arg(long)]
+        rooftop_only: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+    },
+    /// Geocode every row of a CSV file.
+    Batch {
+        input: PathBuf,
+        #[command(flatten)]
+        columns: ColumnArgs,
+        /// Where to write the results. Defaults to stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Number of rows sent in each batch request (at most 10,000).
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..=10_000))]
+        chunk_size: u64,
+        #[command(flatten)]
+        query: QueryArgs,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Csv)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = true)]
+struct ColumnArgs {
+    /// Column holding the full address.
+    #[arg(long, conflicts_with_all = ["line_1_column", "line_2_column", "city_column", "state_column", "postal_code_column", "country_column"])]
+    address_column: Option<String>,
+    /// Column holding the street number, name and suffix.
+    #[arg(long)]
+    line_1_column: Option<String>,
+    #[arg(long)]
+    line_2_column: Option<String>,
+    #[arg(long)]
+    city_column: Option<String>,
+    #[arg(long)]
+    state_column: Option<String>,
+    #[arg(long)]
+    postal_code_column: Option<String>,
+    #[arg(long)]
+    country_column: Option<String>,
+}
+
+impl ColumnArgs {
+    fn mapping(self) -> ColumnMapping {
+        match self.address_column {
+            Some(column) => ColumnMapping::Single(column),
+            None => ColumnMapping::Components(AddressColumns {
+                line_1: self.line_1_column,
+                line_2: self.line_2_column,
+                city: self.city_column,
+                state: self.state_column,
+                country: self.country_column,
+                postal_code: self.postal_code_column,
+            }),
+        }
+    }
+}
+
+#[derive(Args)]
+struct QueryArgs {
+    /// Comma separated field appends, e.g. timezone,cd.
+    #[arg(long, value_delimiter = ',')]
+    fields: Vec<String>,
+    /// Maximum number of results for each query.
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+impl QueryArgs {
+    fn options(&self) -> GeocodeOptions {
+        let fields = self.fields.iter().map(String::as_str).collect::<Vec<_>>();
+        let options = GeocodeOptions::new().fields(&fields);
+        match self.limit {
+            Some(limit) => options.limit(limit),
+            None => options,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Error> {
+    let mut geocodio = match cli.api_key {
+        Some(api_key) => GeocodioProxy::new_from_key(api_key)?,
+        None => GeocodioProxy::new()?,
+    };
+    if let Some(base_url) = cli.base_url {
+        geocodio.base_url = base_url;
+    }
+
+    match cli.command {
+        Command::Geocode { address, query, format } => {
+            let response = geocodio
+                .geocode_with_options(AddressParams::String(address), &query.options())
+                .await?;
+            write_addresses(io::stdout().lock(), &response.results, &response, &query.fields, format)
+        }
+        Command::Reverse { coordinates, query, max_distance_km, rooftop_only, format } => {
+            let coordinates = coordinates.parse::<Coordinates>()?;
+            let mut options = query.options().rooftop_only(rooftop_only);
+            if let Some(max_distance_km) = max_distance_km {
+                options = options.max_distance_km(max_distance_km);
+            }
+            let response = geocodio
+                .reverse_geocode_with_options(coordinates, &options)
+                .await?;
+            let results = response.results.clone().unwrap_or_default();
+            write_addresses(io::stdout().lock(), &results, &response, &query.fields, format)
+        }
+        Command::Batch { input, columns, output, chunk_size, query, format } => {
+            let output: Box<dyn Write> = match output {
+                Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+            let options = CsvOptions::new(columns.mapping())
+                .geocode(query.options())
+                .chunk_size(chunk_size as usize);
+            batch(&geocodio, File::open(input)?, output, &options, format).await
+        }
+    }
+}
+
+/// Write the results of a single geocode or reverse geocode call.
+fn write_addresses<W: Write, T: serde::Serialize>(mut out: W, results: &[Address], response: &T, fields: &[String], format: OutputFormat) -> Result<(), Error> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, response)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Ndjson => {
+            for result in results {
+                serde_json::to_writer(&mut out, result)?;
+                writeln!(out)?;
+            }
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(result_headers(fields))?;
+            for result in results {
+                writer.write_record(address_record(result, fields))?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+async fn batch(geocodio: &GeocodioProxy, input: File, mut out: Box<dyn Write>, options: &CsvOptions, format: OutputFormat) -> Result<(), Error> {
+    let progress = |done, total| eprintln!("Geocoded {}/{} rows", done, total);
+    if let OutputFormat::Csv = format {
+        geocode_csv(geocodio, input, out, options, progress).await?;
+        return Ok(());
+    }
+
+    let CsvRows { headers, rows, addresses } = read_addresses(input, &options.mapping)?;
+    let mut addresses = addresses.into_iter();
+    let mut done = 0;
+    if let OutputFormat::Json = format {
+        write!(out, "[")?;
+    }
+    for chunk in rows.chunks(options.chunk_size) {
+        let batch = addresses.by_ref().take(chunk.len()).collect();
+        let response = match geocodio.geocode_batch_with_options(batch, &options.geocode).await {
+            Ok(response) => response,
+            Err(error) => {
+                // Leave the rows written so far as valid json before giving up.
+                if let OutputFormat::Json = format {
+                    writeln!(out, "\n]")?;
+                }
+                out.flush()?;
+                return Err(error);
+            }
+        };
+        let results = response.results.as_deref().unwrap_or_default();
+        for (i, row) in chunk.iter().enumerate() {
+            let best = results
+                .get(i)
+                .and_then(|result| result.response.as_ref())
+                .and_then(|response| response.results.as_ref())
+                .and_then(|results| results.first());
+            let error = response.rejected.iter().find(|rejection| rejection.index == i).map(|rejection| rejection.error.to_string());
+            let record = json!({
+                "row": headers.iter().zip(row.iter()).map(|(header, value)| (header.to_string(), json!(value))).collect::<serde_json::Map<_, _>>(),
+                "result": best,
+                "error": error,
+            });
+            match format {
+                OutputFormat::Json if done + i > 0 => writeln!(out, ",")?,
+                OutputFormat::Json => writeln!(out)?,
+                _ => {}
+            }
+            serde_json::to_writer(&mut out, &record)?;
+            if let OutputFormat::Ndjson = format {
+                writeln!(out)?;
+            }
+        }
+        done += chunk.len();
+        progress(done, rows.len());
+    }
+    if let OutputFormat::Json = format {
+        writeln!(out, "\n]")?;
+    }
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::{
+    errors::Error,
+    middleware::Middleware,
+    request::{
+        address::{AddressParams, Coordinates},
+        api_key::ApiKey,
+        distance::{DistanceLocation, DistanceOptions},
+        keys::KeyPool,
+        options::GeocodeOptions,
+    },
+    response::{
+        distance::{DistanceMatrixResponse, DistanceResponse},
+        GeocodeBatchResponse, GeocodeResponse, GeocodeReverseResponse, GeocodeSimpleResponse,
+    },
+    usage::{Pricing, Usage},
+};
+
+/// A blocking version of [`crate::GeocodioProxy`] for synchronous code.
+/// 
+/// Every method mirrors the async method with the same name, running it to completion 
+/// on a runtime owned by the proxy. Don't use it from within an async runtime, call 
+/// the async [`crate::GeocodioProxy`] there instead.
+/// 
+/// ```rust,no_run
+/// use geocodio_lib_rust::{blocking::GeocodioProxy, request::address::AddressParams};
+///
+/// fn main() {
+///     let geocodio = GeocodioProxy::new().unwrap();
+///     let response = geocodio
+///         .geocode(
+///             AddressParams::String("1500 Sugar Bowl Dr, New Orleans, LA 70112".to_string()),
+///             None,
+///         )
+///         .unwrap();
+///     println!("{:?}", response.results[0].location);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GeocodioProxy {
+    inner: crate::GeocodioProxy,
+    runtime: tokio::runtime::Runtime,
+}
+
+// ========== instantiate GeocodeProxy ==========
+impl GeocodioProxy {
+    /// Create a new instance of [`GeocodioProxy`] via a variable named `GEOCODIO_API_KEY` you define in a `.env` file.
+    pub fn new() -> Result<Self, Error> {
+        Self::from_async(crate::GeocodioProxy::new()?)
+    }
+
+    /// Create a new instance of [`GeocodioProxy`] via a variable you pass into the method.
+    pub fn new_from_key(api_key: String) -> Result<Self, Error> {
+        Self::from_async(crate::GeocodioProxy::new_from_key(api_key)?)
+    }
+
+    /// Create a new instance of [`GeocodioProxy`] that uses several API keys.
+    pub fn new_from_pool(keys: KeyPool) -> Result<Self, Error> {
+        Self::from_async(crate::GeocodioProxy::new_from_pool(keys)?)
+    }
+
+    /// Wrap an async [`crate::GeocodioProxy`], keeping its keys and settings.
+    pub fn from_async(inner: crate::GeocodioProxy) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(GeocodioProxy { inner, runtime })
+    }
+
+    /// The async [`crate::GeocodioProxy`] used to make the requests.
+    pub fn inner(&self) -> &crate::GeocodioProxy {
+        &self.inner
+    }
+
+    /// The API key used to authorize requests, or the primary key of a [`KeyPool`].
+    pub fn api_key(&self) -> &ApiKey {
+        self.inner.api_key()
+    }
+
+    /// The API keys used to authorize requests.
+    pub fn keys(&self) -> &KeyPool {
+        self.inner.keys()
+    }
+
+    /// See [`crate::GeocodioProxy::with_middleware`].
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.inner = self.inner.with_middleware(middleware);
+        self
+    }
+}
+
+// ========== usage ==========
+impl GeocodioProxy {
+    /// See [`crate::GeocodioProxy::with_budget`].
+    pub fn with_budget(mut self, monthly_lookups: u64) -> Self {
+        self.inner = self.inner.with_budget(monthly_lookups);
+        self
+    }
+
+    /// See [`crate::GeocodioProxy::with_pricing`].
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.inner = self.inner.with_pricing(pricing);
+        self
+    }
+
+    /// Lookups used this month, per endpoint, with an estimated cost.
+    pub fn usage(&self) -> Usage {
+        self.inner.usage()
+    }
+
+    /// Start counting lookups from zero.
+    pub fn reset_usage(&self) {
+        self.inner.reset_usage()
+    }
+}
+
+// ========== geocode address(es) ==========
+impl GeocodioProxy {
+    /// Geocode a single address.
+    pub fn geocode(&self, address: AddressParams, fields: Option<&[&str]>) -> Result<GeocodeResponse, Error> {
+        self.runtime.block_on(self.inner.geocode(address, fields))
+    }
+
+    /// Geocode a single address with [`GeocodeOptions`], such as a result `limit`.
+    pub fn geocode_with_options(&self, address: AddressParams, options: &GeocodeOptions) -> Result<GeocodeResponse, Error> {
+        self.runtime.block_on(self.inner.geocode_with_options(address, options))
+    }
+
+    /// Geocode a single address and only return the best match in the compact `format=simple` layout.
+    pub fn geocode_simple(&self, address: AddressParams, options: &GeocodeOptions) -> Result<GeocodeSimpleResponse, Error> {
+        self.runtime.block_on(self.inner.geocode_simple(address, options))
+    }
+
+    /// Geocode a single address and return the response as untyped json.
+    pub fn geocode_raw(&self, address: AddressParams, options: &GeocodeOptions) -> Result<serde_json::Value, Error> {
+        self.runtime.block_on(self.inner.geocode_raw(address, options))
+    }
+
+    /// Batch Geocode up to [10,000 addresses](https://www.geocod.io/docs/#batch-geocoding).
+    pub fn geocode_batch(&self, addresses: Vec<AddressParams>) -> Result<GeocodeBatchResponse, Error> {
+        self.runtime.block_on(self.inner.geocode_batch(addresses))
+    }
+
+    /// Batch Geocode up to [10,000 addresses](https://www.geocod.io/docs/#batch-geocoding) with [`GeocodeOptions`].
+    pub fn geocode_batch_with_options(&self, addresses: Vec<AddressParams>, options: &GeocodeOptions) -> Result<GeocodeBatchResponse, Error> {
+        self.runtime.block_on(self.inner.geocode_batch_with_options(addresses, options))
+    }
+}
+
+// ========== reverse geocode ==========
+impl GeocodioProxy {
+    /// Reverse geocode [`Coordinates`] to get the address and other location information.
+    pub fn reverse_geocode(&self, coordinates: impl Into<Coordinates>) -> Result<GeocodeReverseResponse, Error> {
+        self.runtime.block_on(self.inner.reverse_geocode(coordinates))
+    }
+
+    /// Reverse geocode [`Coordinates`] with [`GeocodeOptions`], such as a result `limit`.
+    pub fn reverse_geocode_with_options(&self, coordinates: impl Into<Coordinates>, options: &GeocodeOptions) -> Result<GeocodeReverseResponse, Error> {
+        self.runtime.block_on(self.inner.reverse_geocode_with_options(coordinates, options))
+    }
+
+    /// Reverse geocode [`Coordinates`] and only return the closest match in the compact `format=simple` layout.
+    pub fn reverse_geocode_simple(&self, coordinates: impl Into<Coordinates>, options: &GeocodeOptions) -> Result<GeocodeSimpleResponse, Error> {
+        self.runtime.block_on(self.inner.reverse_geocode_simple(coordinates, options))
+    }
+
+    /// Reverse geocode a vector of [`Coordinates`] to get the addresses and other location information.
+    pub fn reverse_geocode_batch<C: Into<Coordinates>>(&self, coordinates: impl IntoIterator<Item = C>) -> Result<GeocodeBatchResponse, Error> {
+        self.runtime.block_on(self.inner.reverse_geocode_batch(coordinates))
+    }
+
+    /// Reverse geocode a vector of [`Coordinates`] with [`GeocodeOptions`].
+    pub fn reverse_geocode_batch_with_options<C: Into<Coordinates>>(&self, coordinates: impl IntoIterator<Item = C>, options: &GeocodeOptions) -> Result<GeocodeBatchResponse, Error> {
+        self.runtime.block_on(self.inner.reverse_geocode_batch_with_options(coordinates, options))
+    }
+}
+
+// ========== distance ==========
+impl GeocodioProxy {
+    /// Calculate the distance from one origin to each of the destinations.
+    pub fn distance(&self, origin: impl Into<DistanceLocation>, destinations: Vec<DistanceLocation>, options: &DistanceOptions) -> Result<DistanceResponse, Error> {
+        self.runtime.block_on(self.inner.distance(origin, destinations, options))
+    }
+
+    /// Calculate the distance from every origin to every destination.
+    pub fn distance_matrix(&self, origins: Vec<DistanceLocation>, destinations: Vec<DistanceLocation>, options: &DistanceOptions) -> Result<DistanceMatrixResponse, Error> {
+        self.runtime.block_on(self.inner.distance_matrix(origins, destinations, options))
+    }
+}
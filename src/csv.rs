@@ -0,0 +1,324 @@
+use std::{collections::HashSet, io::{Read, Write}};
+
+use ::csv::{Reader, StringRecord, Writer};
+
+use crate::{
+    errors::Error,
+    request::{address::{AddressInput, AddressParams}, options::GeocodeOptions},
+    response::{address::Address, utils::Fields, ResponseResult},
+    GeocodioProxy,
+};
+
+/// Columns describing the location of each result, added after the original columns.
+pub const RESULT_COLUMNS: [&str; 5] = ["latitude", "longitude", "accuracy", "accuracy_type", "formatted_address"];
+
+/// Which columns of a CSV file make up the address.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnMapping {
+    /// The whole address is in a single column.
+    Single(String),
+    /// The address is split across several columns.
+    Components(AddressColumns),
+}
+
+/// Column names for each part of an [`AddressInput`]. Parts without a column are left empty.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AddressColumns {
+    pub line_1: Option<String>,
+    pub line_2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+    pub postal_code: Option<String>,
+}
+
+/// Options for [`geocode_csv`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub mapping: ColumnMapping,
+    /// Options sent with each batch, including the field appends that are written
+    /// out as extra columns.
+    pub geocode: GeocodeOptions,
+    /// Number of rows sent in each batch request, at most 10,000.
+    pub chunk_size: usize,
+}
+
+impl CsvOptions {
+    pub fn new(mapping: ColumnMapping) -> Self {
+        CsvOptions { mapping, geocode: GeocodeOptions::new(), chunk_size: 1000 }
+    }
+
+    pub fn geocode(mut self, options: GeocodeOptions) -> Self {
+        self.geocode = options;
+        self
+    }
+
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.clamp(1, 10_000);
+        self
+    }
+}
+
+/// The rows of a CSV file along with the address read from each row.
+pub struct CsvRows {
+    pub headers: StringRecord,
+    pub rows: Vec<StringRecord>,
+    pub addresses: Vec<AddressParams>,
+}
+
+/// Read every row of a CSV file with headers into [`AddressParams`].
+pub fn read_addresses<R: Read>(reader: R, mapping: &ColumnMapping) -> Result<CsvRows, Error> {
+    let mut reader = Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| Error::MissingColumn(name.to_string()))
+    };
+    let optional_column = |name: &Option<String>| name.as_deref().map(column).transpose();
+
+    let rows = reader.records().collect::<Result<Vec<_>, _>>()?;
+    let addresses = match mapping {
+        ColumnMapping::Single(name) => {
+            let i = column(name)?;
+            rows.iter()
+                .map(|row| AddressParams::String(row.get(i).unwrap_or_default().to_string()))
+                .collect()
+        }
+        ColumnMapping::Components(columns) => {
+            let line_1 = optional_column(&columns.line_1)?;
+            let line_2 = optional_column(&columns.line_2)?;
+            let city = optional_column(&columns.city)?;
+            let state = optional_column(&columns.state)?;
+            let country = optional_column(&columns.country)?;
+            let postal_code = optional_column(&columns.postal_code)?;
+            let get = |row: &StringRecord, i: Option<usize>| {
+                i.and_then(|i| row.get(i)).filter(|value| !value.trim().is_empty()).map(str::to_string)
+            };
+            rows.iter()
+                .map(|row| AddressParams::AddressInput(AddressInput {
+                    line_1: get(row, line_1),
+                    line_2: get(row, line_2),
+                    city: get(row, city),
+                    state: get(row, state),
+                    country: get(row, country),
+                    postal_code: get(row, postal_code),
+                }))
+                .collect()
+        }
+    };
+    Ok(CsvRows { headers, rows, addresses })
+}
+
+/// Batch geocode every row of a CSV file and write each original row followed by the
+/// [`RESULT_COLUMNS`] and the columns for the requested field appends.
+///
+/// `progress` is called after each chunk with the number of rows done and the total.
+/// Returns the number of rows written.
+///
+/// ```rust,no_run
+/// use std::fs::File;
+/// use geocodio_lib_rust::{csv::{geocode_csv, ColumnMapping, CsvOptions}, request::options::GeocodeOptions, GeocodioProxy};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let geocodio = GeocodioProxy::new().unwrap();
+///     let options = CsvOptions::new(ColumnMapping::Single("address".to_string()))
+///         .geocode(GeocodeOptions::new().fields(&["timezone"]));
+///     let rows = geocode_csv(
+///         &geocodio,
+///         File::open("input.csv").unwrap(),
+///         File::create("output.csv").unwrap(),
+///         &options,
+///         |done, total| eprintln!("{}/{}", done, total),
+///     )
+///     .await
+///     .unwrap();
+///     println!("Geocoded {} rows", rows);
+/// }
+/// ```
+pub async fn geocode_csv<R: Read, W: Write>(
+    geocodio: &GeocodioProxy,
+    reader: R,
+    writer: W,
+    options: &CsvOptions,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<usize, Error> {
+    let CsvRows { headers, rows, addresses } = read_addresses(reader, &options.mapping)?;
+    let fields = &options.geocode.fields;
+    let mut writer = Writer::from_writer(writer);
+    writer.write_record(headers.iter().map(str::to_string).chain(result_headers(fields)))?;
+
+    let mut addresses = addresses.into_iter();
+    let mut done = 0;
+    for chunk in rows.chunks(options.chunk_size.max(1)) {
+        let batch = addresses.by_ref().take(chunk.len()).collect();
+        let response = geocodio.geocode_batch_with_options(batch, &options.geocode).await?;
+        let results = response.results.unwrap_or_default();
+        for (i, row) in chunk.iter().enumerate() {
+            let best = results
+                .get(i)
+                .and_then(|result| result.response.as_ref())
+                .and_then(|response| response.results.as_ref())
+                .and_then(|results| results.first());
+            writer.write_record(row.iter().map(str::to_string).chain(result_record(best, fields)))?;
+        }
+        done += chunk.len();
+        progress(done, rows.len());
+    }
+    writer.flush()?;
+    Ok(done)
+}
+
+/// Headers for [`RESULT_COLUMNS`] followed by the columns of each field append, named
+/// as requested or by their response key, see [`Fields::key`]. Field appends that
+/// share columns, such as `cd` and `cd118`, only get them once.
+///
+/// ```rust
+/// use geocodio_lib_rust::csv::result_headers;
+///
+/// let headers = result_headers(&["cd".to_string(), "cd118".to_string()]);
+/// assert_eq!(headers.iter().filter(|header| *header == "congressional_district").count(), 1);
+/// ```
+pub fn result_headers(fields: &[String]) -> Vec<String> {
+    RESULT_COLUMNS
+        .iter()
+        .map(|column| column.to_string())
+        .chain(column_fields(fields).into_iter().flat_map(field_headers))
+        .collect()
+}
+
+/// Flatten a batch result into the columns named by [`result_headers`]. A missing
+/// result gives empty columns.
+pub fn result_record(result: Option<&ResponseResult>, fields: &[String]) -> Vec<String> {
+    let location = result.and_then(|result| result.location.as_ref());
+    let mut record = vec![
+        location.and_then(|location| location.lat).map(|lat| lat.to_string()).unwrap_or_default(),
+        location.and_then(|location| location.lng).map(|lng| lng.to_string()).unwrap_or_default(),
+        result.and_then(|result| result.accuracy).map(|accuracy| accuracy.to_string()).unwrap_or_default(),
+        result.and_then(|result| result.accuracy_type.clone()).unwrap_or_default(),
+        result.and_then(|result| result.formatted_address.clone()).unwrap_or_default(),
+    ];
+    let appended = result.and_then(|result| result.fields.as_ref());
+    record.extend(column_fields(fields).into_iter().flat_map(|field| field_values(field, appended)));
+    record
+}
+
+/// Flatten an [`Address`] into the columns named by [`result_headers`].
+pub fn address_record(address: &Address, fields: &[String]) -> Vec<String> {
+    let mut record = vec![
+        address.location.latitude.to_string(),
+        address.location.longitude.to_string(),
+        address.accuracy.to_string(),
+        address.accuracy_type.clone(),
+        address.formatted_address.clone(),
+    ];
+    record.extend(column_fields(fields).into_iter().flat_map(|field| field_values(field, address.fields.as_ref())));
+    record
+}
+
+/// The kinds of field appends with their own columns. Anything else is written as json.
+enum FieldKind {
+    Timezone,
+    CongressionalDistrict,
+    StateLegislativeDistricts,
+    SchoolDistricts,
+    Zip4,
+    Census,
+    Acs,
+    Other,
+}
+
+impl FieldKind {
+    fn of(field: &str) -> Self {
+        match Fields::key(field) {
+            "timezone" => FieldKind::Timezone,
+            "zip4" => FieldKind::Zip4,
+            "school_districts" => FieldKind::SchoolDistricts,
+            "congressional_districts" => FieldKind::CongressionalDistrict,
+            "state_legislative_districts" => FieldKind::StateLegislativeDistricts,
+            "census" => FieldKind::Census,
+            "acs" => FieldKind::Acs,
+            _ => FieldKind::Other,
+        }
+    }
+}
+
+/// The field appends that get columns, leaving out those whose columns repeat an
+/// earlier one's.
+fn column_fields(fields: &[String]) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    fields.iter().map(String::as_str).filter(|field| seen.insert(field_headers(field))).collect()
+}
+
+fn field_headers(field: &str) -> Vec<String> {
+    let columns: &[&str] = match FieldKind::of(field) {
+        FieldKind::Timezone => &["timezone", "timezone_abbreviation", "timezone_utc_offset", "timezone_observes_dst"],
+        FieldKind::CongressionalDistrict => &["congressional_district", "congressional_district_number"],
+        FieldKind::StateLegislativeDistricts => &["state_house_district", "state_senate_district"],
+        FieldKind::SchoolDistricts => &["school_district_unified", "school_district_elementary", "school_district_secondary"],
+        FieldKind::Zip4 => &["zip9", "zip4_record_type"],
+        FieldKind::Census | FieldKind::Acs | FieldKind::Other => return vec![field.to_string()],
+    };
+    columns.iter().map(|column| column.to_string()).collect()
+}
+
+fn field_values(field: &str, fields: Option<&Fields>) -> Vec<String> {
+    let len = field_headers(field).len();
+    let Some(fields) = fields else {
+        return vec![String::new(); len];
+    };
+    let text = |value: Option<String>| value.unwrap_or_default();
+    match FieldKind::of(field) {
+        FieldKind::Timezone => {
+            let timezone = fields.timezone.as_ref();
+            vec![
+                text(timezone.map(|timezone| timezone.name.clone())),
+                text(timezone.map(|timezone| timezone.abbreviation.clone())),
+                text(timezone.map(|timezone| timezone.utc_offset.to_string())),
+                text(timezone.map(|timezone| timezone.observes_dst.to_string())),
+            ]
+        }
+        FieldKind::CongressionalDistrict => {
+            let district = fields
+                .congressional_districts
+                .as_ref()
+                .and_then(|districts| districts.first())
+                .or(fields.congressional_district.as_ref());
+            vec![
+                text(district.map(|district| district.name.clone())),
+                text(district.map(|district| district.district_number.to_string())),
+            ]
+        }
+        FieldKind::StateLegislativeDistricts => {
+            let districts = fields.state_legislative_districts.as_ref();
+            vec![
+                text(districts.and_then(|districts| districts.house.first()).map(|district| district.district_number.clone())),
+                text(districts.and_then(|districts| districts.senate.first()).map(|district| district.district_number.clone())),
+            ]
+        }
+        FieldKind::SchoolDistricts => {
+            let districts = fields.school_districts.as_ref();
+            vec![
+                text(districts.and_then(|districts| districts.unified.as_ref()).map(|district| district.name.clone())),
+                text(districts.and_then(|districts| districts.elementary.as_ref()).map(|district| district.name.clone())),
+                text(districts.and_then(|districts| districts.secondary.as_ref()).map(|district| district.name.clone())),
+            ]
+        }
+        FieldKind::Zip4 => {
+            let zip4 = fields.zip4.as_ref();
+            vec![
+                text(zip4.and_then(|zip4| zip4.zip9.first().cloned())),
+                text(zip4.map(|zip4| zip4.record_type.description.clone())),
+            ]
+        }
+        FieldKind::Census => vec![json(fields.census.as_ref())],
+        FieldKind::Acs => vec![json(fields.acs.as_ref())],
+        FieldKind::Other => vec![json(fields.extra.get(Fields::key(field)))],
+    }
+}
+
+fn json(value: Option<&serde_json::Value>) -> String {
+    value.as_ref().map(|value| value.to_string()).unwrap_or_default()
+}
@@ -12,12 +12,89 @@ pub enum Error {
     #[error(transparent)]
     BadInputData(#[from] serde_json::Error),
 
+    #[error("Failed to decode the {endpoint} response at {path}: {message} (near {excerpt})")]
+    Decode { endpoint: String, path: String, message: String, excerpt: String },
+
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    Csv(#[from] ::csv::Error),
+
+    #[error("Column {0} is not in the CSV headers")]
+    MissingColumn(String),
+
     #[error("Failed to fetch {0} from Geocodio API")]
     Api(String),
 
+    #[error("Geocodio API responded with {status}: {message}")]
+    Status { status: u16, message: String },
+
     #[error("Missing required API key")]
     MissingAPIKey,
 
+    #[error("No API key is assigned to tenant {0}")]
+    UnknownTenant(String),
+
+    #[error("No API key was added with the label {0}")]
+    UnknownKeyLabel(String),
+
     #[error("Address cannot be empty")]
     EmptyAddress,
-}
\ No newline at end of file
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(ValidationError),
+
+    #[error("Monthly budget of {limit} lookups exceeded ({used} used, {requested} requested)")]
+    BudgetExceeded { limit: u64, used: u64, requested: u64 },
+
+    #[error("Invalid coordinates: {0}")]
+    InvalidCoordinates(String),
+}
+
+impl Error {
+    /// A short, stable name for the kind of error, used to label logs and metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "io",
+            Error::Var(_) => "var",
+            Error::Request(_) => "request",
+            Error::BadInputData(_) | Error::Decode { .. } => "decode",
+            #[cfg(feature = "csv")]
+            Error::Csv(_) => "csv",
+            Error::MissingColumn(_) => "missing_column",
+            Error::Api(_) => "api",
+            Error::Status { .. } => "status",
+            Error::MissingAPIKey => "missing_api_key",
+            Error::UnknownTenant(_) => "unknown_tenant",
+            Error::UnknownKeyLabel(_) => "unknown_key_label",
+            Error::EmptyAddress => "empty_address",
+            Error::InvalidAddress(_) => "invalid_address",
+            Error::BudgetExceeded { .. } => "budget_exceeded",
+            Error::InvalidCoordinates(_) => "invalid_coordinates",
+        }
+    }
+}
+
+/// Why an address was rejected before being sent to the API.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("Address cannot be empty")]
+    Empty,
+
+    #[error("{0} is not a valid postal code")]
+    PostalCode(String),
+
+    #[error("{0} is not a known state or province")]
+    State(String),
+
+    #[error("{0} is not a supported country")]
+    Country(String),
+}
+
+impl From<ValidationError> for Error {
+    fn from(error: ValidationError) -> Self {
+        match error {
+            ValidationError::Empty => Error::EmptyAddress,
+            error => Error::InvalidAddress(error),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use geo_types::{Coord, Point};
+
+use crate::{
+    errors::Error,
+    request::{address::Coordinates, distance::DistanceLocation},
+    response::utils::Location,
+};
+
+impl From<Coordinates> for Point<f64> {
+    fn from(coordinates: Coordinates) -> Self {
+        Point::new(coordinates.longitude, coordinates.latitude)
+    }
+}
+
+impl From<&Coordinates> for Point<f64> {
+    fn from(coordinates: &Coordinates) -> Self {
+        Point::new(coordinates.longitude, coordinates.latitude)
+    }
+}
+
+impl From<Point<f64>> for Coordinates {
+    fn from(point: Point<f64>) -> Self {
+        Coordinates { latitude: point.y(), longitude: point.x() }
+    }
+}
+
+impl From<Coordinates> for Coord<f64> {
+    fn from(coordinates: Coordinates) -> Self {
+        Coord { x: coordinates.longitude, y: coordinates.latitude }
+    }
+}
+
+impl From<&Coordinates> for Coord<f64> {
+    fn from(coordinates: &Coordinates) -> Self {
+        Coord { x: coordinates.longitude, y: coordinates.latitude }
+    }
+}
+
+impl From<Coord<f64>> for Coordinates {
+    fn from(coord: Coord<f64>) -> Self {
+        Coordinates { latitude: coord.y, longitude: coord.x }
+    }
+}
+
+impl From<Point<f64>> for Location {
+    fn from(point: Point<f64>) -> Self {
+        Location { lat: Some(point.y()), lng: Some(point.x()) }
+    }
+}
+
+impl From<Coord<f64>> for Location {
+    fn from(coord: Coord<f64>) -> Self {
+        Location { lat: Some(coord.y), lng: Some(coord.x) }
+    }
+}
+
+/// Fails when the batch result is missing its latitude or longitude.
+impl TryFrom<&Location> for Point<f64> {
+    type Error = Error;
+
+    fn try_from(location: &Location) -> Result<Self, Self::Error> {
+        Coord::try_from(location).map(Point::from)
+    }
+}
+
+/// Fails when the batch result is missing its latitude or longitude.
+impl TryFrom<&Location> for Coord<f64> {
+    type Error = Error;
+
+    fn try_from(location: &Location) -> Result<Self, Self::Error> {
+        match (location.lat, location.lng) {
+            (Some(y), Some(x)) => Ok(Coord { x, y }),
+            _ => Err(Error::InvalidCoordinates("location is missing its latitude or longitude".to_string())),
+        }
+    }
+}
+
+impl From<Point<f64>> for DistanceLocation {
+    fn from(point: Point<f64>) -> Self {
+        DistanceLocation::Coordinates(point.into())
+    }
+}
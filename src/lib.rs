@@ -1,8 +1,18 @@
 #![doc = include_str!("../README.md")]
 
 use errors::Error;
-use response::{GeocodeBatchResponse, GeocodeResponse, GeocodeReverseResponse};
-use request::{address::{AddressParams, Coordinates}, fetch::{batch_fetch, proxy_new}};
+use middleware::{Middleware, MiddlewareChain};
+use usage::{Pricing, Usage, UsageTracker};
+use response::{distance::{DistanceMatrixResponse, DistanceResponse}, BatchRejection, BatchResult, GeocodeBatchResponse, GeocodeResponse, GeocodeReverseResponse, GeocodeSimpleResponse};
+use request::{
+    address::{AddressParams, Coordinates},
+    api_key::ApiKey,
+    keys::KeyPool,
+    coordinates::COORDINATE_PRECISION,
+    distance::{all_coordinates, is_offline, DistanceLocation, DistanceOptions},
+    fetch::{batch_fetch, decode_raw, matrix_fetch, proxy_new},
+    options::GeocodeOptions,
+};
 
 /// Response structs formatted from the json in the API docs
 pub mod response;
@@ -10,6 +20,26 @@ pub mod response;
 pub mod errors;
 /// Request structs and functions 
 pub mod request;
+/// Comparing inputs with geocoded results to decide whether they match
+pub mod matching;
+/// Lookup accounting, budgets and cost estimates
+pub mod usage;
+/// Hooks around requests and responses
+pub mod middleware;
+/// Spans and metrics, enabled with the `tracing` and `metrics` features
+mod telemetry;
+/// CSV input and output adapters, enabled with the `csv` feature
+#[cfg(feature = "csv")]
+pub mod csv;
+/// Nearest-location and radius queries over geocoded addresses, enabled with the `nearby` feature
+#[cfg(feature = "nearby")]
+pub mod nearby;
+/// Conversions to and from `geo-types`, enabled with the `geo-types` feature
+#[cfg(feature = "geo-types")]
+mod geo;
+/// A synchronous client, enabled with the `blocking` feature
+#[cfg(feature = "blocking")]
+pub mod blocking;
 
 /// A struct used to interface with the [Geocodio API](https://www.geocod.io/docs/#introduction).
 /// 
@@ -17,7 +47,7 @@ pub mod request;
 /// API key. There's an option if you have it in a .env file with the name 'GEOCODIO_API_KEY' 
 /// ([`GeocodioProxy::new`]), or if you're using another method to obtain your key and 
 /// assigning it to a variable ([`GeocodioProxy::new_from_key()`]).
-/// ```rust
+/// ```rust,ignore
 /// let geocodio = GeocodioProxy::new().unwrap();
 /// // or
 /// let geocodio = GeocodioProxy::new_from_key(my_api_key).unwrap();
@@ -26,10 +56,17 @@ pub mod request;
 /// Once you instantiate the struct, you can either geocode or reverse geocode 
 /// a single address or a batch of addresses 
 /// [(up to 10,000 lookups)](https://www.geocod.io/docs/#batch-geocoding).
+/// 
+/// The API key is sent in the `Authorization` header and is redacted from `Debug` output.
+/// If you have more than one Geocodio account, use [`GeocodioProxy::new_from_pool`] with
+/// a [`KeyPool`] to rotate between keys and fail over when one is rejected.
+#[derive(Debug)]
 pub struct GeocodioProxy {
     pub client: reqwest::Client,
     pub base_url: reqwest::Url,
-    pub api_key: String,
+    keys: KeyPool,
+    usage: UsageTracker,
+    middleware: MiddlewareChain,
 }
 
 // ========== instantiate GeocodeProxy ==========
@@ -38,12 +75,62 @@ impl GeocodioProxy {
     pub fn new() -> Result<Self, Error> {
         dotenv::dotenv().ok();
         let api_key = std::env::var("GEOCODIO_API_KEY")?;
-        proxy_new(api_key)
+        proxy_new(ApiKey::new(api_key).into())
     }
 
     /// Create a new instance of [`GeocodioProxy`] via a variable you pass into the method.
     pub fn new_from_key(api_key: String) -> Result<Self, Error> {
-        proxy_new(api_key)
+        proxy_new(ApiKey::new(api_key).into())
+    }
+
+    /// Create a new instance of [`GeocodioProxy`] that uses several API keys.
+    pub fn new_from_pool(keys: KeyPool) -> Result<Self, Error> {
+        proxy_new(keys)
+    }
+
+    /// The API key used to authorize requests, or the primary key of a [`KeyPool`].
+    pub fn api_key(&self) -> &ApiKey {
+        self.keys.primary().expect("a GeocodioProxy always has at least one key")
+    }
+
+    /// The API keys used to authorize requests.
+    pub fn keys(&self) -> &KeyPool {
+        &self.keys
+    }
+
+    /// Add a [`Middleware`] to run around every call, after the ones already added.
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(std::sync::Arc::new(middleware));
+        self
+    }
+}
+
+// ========== usage ==========
+impl GeocodioProxy {
+    /// Refuse calls with [`Error::BudgetExceeded`] once they would use more than
+    /// `monthly_lookups` lookups in the current month.
+    pub fn with_budget(self, monthly_lookups: u64) -> Self {
+        self.usage.set_budget(Some(monthly_lookups));
+        self
+    }
+
+    /// Set the [`Pricing`] used to estimate the cost of lookups.
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.usage.pricing = pricing;
+        self
+    }
+
+    /// Lookups used this month, per endpoint, with an estimated cost.
+    /// 
+    /// Batch calls count each address or coordinate as a lookup, and every field append 
+    /// counts as an additional lookup per item.
+    pub fn usage(&self) -> Usage {
+        self.usage.snapshot()
+    }
+
+    /// Start counting lookups from zero.
+    pub fn reset_usage(&self) {
+        self.usage.reset()
     }
 }
 
@@ -53,7 +140,7 @@ impl GeocodioProxy {
     /// 
     /// # Example
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use geocodio_lib_rust::{request::address::{AddressInput, AddressParams}, GeocodioProxy};
     ///
     /// #[tokio::main]
@@ -81,22 +168,64 @@ impl GeocodioProxy {
     ///}
     /// ```
     pub async fn geocode(&self, address: AddressParams, fields: Option<&[&str]>) -> Result<GeocodeResponse, Error> {
-        let mut params = match address {
-            AddressParams::String(address) => address.to_string(),
-            AddressParams::AddressInput(address) => address.fmt_string(),
+        let options = match fields {
+            Some(fields) => GeocodeOptions::new().fields(fields),
+            None => GeocodeOptions::new(),
         };
-        if let Some(fields) = fields {
-            params.push_str(format!("&fields={}", fields.join(",")).as_str());
-        }
+        self.geocode_with_options(address, &options).await
+    }
+
+    /// Geocode a single address with [`GeocodeOptions`], such as a result `limit`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// use geocodio_lib_rust::{request::{address::AddressParams, options::GeocodeOptions}, GeocodioProxy};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let geocodio = GeocodioProxy::new().unwrap();
+    ///    let options = GeocodeOptions::new().limit(1).fields(&["timezone"]);
+    ///    let response = geocodio
+    ///        .geocode_with_options(
+    ///            AddressParams::String("1500 Sugar Bowl Dr, New Orleans, LA 70112".to_string()),
+    ///            &options,
+    ///        )
+    ///        .await
+    ///        .unwrap();
+    ///    println!("{:?}", response.results);
+    ///}
+    /// ```
+    pub async fn geocode_with_options(&self, address: AddressParams, options: &GeocodeOptions) -> Result<GeocodeResponse, Error> {
+        address.validate()?;
+        let params = address.query_pairs();
         let endpoint = "geocode";
-        single_fetch!(self, endpoint, params, GeocodeResponse)
+        single_fetch!(self, endpoint, params, 1, options, GeocodeResponse)
+    }
+
+    /// Geocode a single address and only return the best match in the compact `format=simple` layout.
+    pub async fn geocode_simple(&self, address: AddressParams, options: &GeocodeOptions) -> Result<GeocodeSimpleResponse, Error> {
+        address.validate()?;
+        let params = address.query_pairs();
+        let options = &options.clone().param("format", "simple");
+        let endpoint = "geocode";
+        single_fetch!(self, endpoint, params, 1, options, GeocodeSimpleResponse)
+    }
+
+    /// Geocode a single address and return the response as untyped json, for fields
+    /// this version of the crate doesn't cover.
+    pub async fn geocode_raw(&self, address: AddressParams, options: &GeocodeOptions) -> Result<serde_json::Value, Error> {
+        address.validate()?;
+        let params = address.query_pairs();
+        let served = self.request("geocode", &params, 1, options).await?;
+        decode_raw(served).await
     }
 
     /// Batch Geocode up to [10,000 addresses](https://www.geocod.io/docs/#batch-geocoding).
     /// 
     /// # Example
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use geocodio_lib_rust::{request::address::AddressParams, response::BatchResult, GeocodioProxy};
     ///
     /// #[tokio::main]
@@ -134,15 +263,43 @@ impl GeocodioProxy {
     ///}
     /// ```
     pub async fn geocode_batch(&self, addresses: Vec<AddressParams>) -> Result<GeocodeBatchResponse, Error> {
-        let mut params: Vec<String> = Vec::new();
-        addresses.iter().for_each(|address| {
-            match address {
-                AddressParams::String(address) => params.push(address.to_string()),
-                AddressParams::AddressInput(address) => params.push(address.to_string()),
-            };
-        });
-        let endpoint = format!("geocode?api_key={}", &self.api_key);
-        batch_fetch(self, endpoint, params).await
+        self.geocode_batch_with_options(addresses, &GeocodeOptions::new()).await
+    }
+
+    /// Batch Geocode up to [10,000 addresses](https://www.geocod.io/docs/#batch-geocoding) with [`GeocodeOptions`].
+    /// 
+    /// Addresses that fail [`AddressParams::validate`] aren't sent. They keep their place
+    /// in `results` with an empty response and are listed in
+    /// [`GeocodeBatchResponse::rejected`].
+    pub async fn geocode_batch_with_options(&self, addresses: Vec<AddressParams>, options: &GeocodeOptions) -> Result<GeocodeBatchResponse, Error> {
+        let mut rejected = Vec::new();
+        let mut params = Vec::new();
+        for (index, address) in addresses.iter().enumerate() {
+            match address.validate() {
+                Ok(()) => params.push(address.to_string()),
+                Err(error) => rejected.push(BatchRejection { index, query: address.to_string(), error }),
+            }
+        }
+        if rejected.is_empty() {
+            return batch_fetch(self, "geocode", params, options).await;
+        }
+
+        let mut response = if params.is_empty() {
+            GeocodeBatchResponse::default()
+        } else {
+            batch_fetch(self, "geocode", params, options).await?
+        };
+        let mut sent = response.results.take().unwrap_or_default().into_iter();
+        let mut rejections = rejected.iter().peekable();
+        let results = (0..addresses.len())
+            .filter_map(|index| match rejections.next_if(|rejection| rejection.index == index) {
+                Some(rejection) => Some(BatchResult { query: Some(rejection.query.clone()), response: None, extra: Default::default() }),
+                None => sent.next(),
+            })
+            .collect();
+        response.results = Some(results);
+        response.rejected = rejected;
+        Ok(response)
     }
 }
 
@@ -152,7 +309,7 @@ impl GeocodioProxy {
     /// 
     /// # Example
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use geocodio_lib_rust::{request::address::Coordinates, GeocodioProxy};
     /// 
     /// #[tokio::main]
@@ -168,17 +325,64 @@ impl GeocodioProxy {
     /// }
     /// ```
     /// 
-    pub async fn reverse_geocode(&self, coordinates: Coordinates) -> Result<GeocodeReverseResponse, Error> {
-        let params = format!("q={},{}", coordinates.latitude, coordinates.longitude);
+    pub async fn reverse_geocode(&self, coordinates: impl Into<Coordinates>) -> Result<GeocodeReverseResponse, Error> {
+        self.reverse_geocode_with_options(coordinates, &GeocodeOptions::new()).await
+    }
+
+    /// Reverse geocode [`Coordinates`] with [`GeocodeOptions`], such as a result `limit`.
+    ///
+    /// Each result has its [`distance_km`](response::address::Address::distance_km) from
+    /// `coordinates` set and results are sorted nearest first, before dropping those
+    /// outside [`GeocodeOptions::max_distance_km`] or that aren't a rooftop or point when
+    /// [`GeocodeOptions::rooftop_only`] is set.
+    ///
+    /// ```rust,no_run
+    /// use geocodio_lib_rust::{request::{address::Coordinates, options::GeocodeOptions}, GeocodioProxy};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let geocodio = GeocodioProxy::new().unwrap();
+    ///     let coordinates = Coordinates { latitude: 40.81352, longitude: -74.074333 };
+    ///     let options = GeocodeOptions::new().limit(3).max_distance_km(0.5).rooftop_only(true);
+    ///
+    ///     let response = geocodio
+    ///         .reverse_geocode_with_options(coordinates, &options)
+    ///         .await
+    ///         .unwrap();
+    ///     for address in response.results.unwrap_or_default() {
+    ///         println!("{} ({:.0} m)", address.formatted_address, address.distance_km.unwrap() * 1000.0);
+    ///     }
+    /// }
+    /// ```
+    pub async fn reverse_geocode_with_options(&self, coordinates: impl Into<Coordinates>, options: &GeocodeOptions) -> Result<GeocodeReverseResponse, Error> {
+        let coordinates = coordinates.into();
+        coordinates.validate()?;
+        let params = [("q", coordinates.to_precision_string(COORDINATE_PRECISION))];
+        let request_options = &options.reverse_request_options();
+        let endpoint = "reverse";
+        let mut response = single_fetch!(self, endpoint, params, 1, request_options, GeocodeReverseResponse)?;
+        response.rank(&coordinates, options);
+        Ok(response)
+    }
+
+    /// Reverse geocode [`Coordinates`] and only return the closest match in the compact `format=simple` layout.
+    pub async fn reverse_geocode_simple(&self, coordinates: impl Into<Coordinates>, options: &GeocodeOptions) -> Result<GeocodeSimpleResponse, Error> {
+        let coordinates = coordinates.into();
+        coordinates.validate()?;
+        let params = [("q", coordinates.to_precision_string(COORDINATE_PRECISION))];
+        let options = &options.clone().param("format", "simple");
         let endpoint = "reverse";
-        single_fetch!(self, endpoint, params, GeocodeReverseResponse)
+        single_fetch!(self, endpoint, params, 1, options, GeocodeSimpleResponse)
     }
 
     /// Reverse geocode a vector of [`Coordinates`] to get the addresses and other location information.
     /// 
+    /// Any iterator of values that convert into [`Coordinates`] is accepted, such as 
+    /// `geo_types::Point`s when the `geo-types` feature is enabled.
+    /// 
     /// # Example
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use geocodio_lib_rust::{request::address::Coordinates, GeocodioProxy};
     ///
     /// #[tokio::main]
@@ -198,11 +402,106 @@ impl GeocodioProxy {
     ///     println!("{:?}", response);
     /// }
     /// ```
-    pub async fn reverse_geocode_batch(&self, coordinates: Vec<Coordinates>) -> Result<GeocodeBatchResponse, Error> {
-        let params = coordinates.iter().map(|coords| {
-                format!("{},{}", coords.latitude, coords.longitude)
-            }).collect::<Vec<String>>();
-        let endpoint = format!("reverse?api_key={}", &self.api_key);
-        batch_fetch(self, endpoint, params).await
-    }
-}
\ No newline at end of file
+    pub async fn reverse_geocode_batch<C: Into<Coordinates>>(&self, coordinates: impl IntoIterator<Item = C>) -> Result<GeocodeBatchResponse, Error> {
+        self.reverse_geocode_batch_with_options(coordinates, &GeocodeOptions::new()).await
+    }
+
+    /// Reverse geocode a vector of [`Coordinates`] with [`GeocodeOptions`].
+    ///
+    /// The results of each query are ranked and filtered the same way as
+    /// [`reverse_geocode_with_options`](GeocodioProxy::reverse_geocode_with_options).
+    pub async fn reverse_geocode_batch_with_options<C: Into<Coordinates>>(&self, coordinates: impl IntoIterator<Item = C>, options: &GeocodeOptions) -> Result<GeocodeBatchResponse, Error> {
+        let coordinates = coordinates.into_iter().map(|coords| {
+                let coords = coords.into();
+                coords.validate()?;
+                Ok(coords)
+            }).collect::<Result<Vec<Coordinates>, Error>>()?;
+        let params = coordinates.iter().map(|coords| coords.to_precision_string(COORDINATE_PRECISION)).collect();
+        let endpoint = "reverse";
+        let mut response = batch_fetch(self, endpoint, params, &options.reverse_request_options()).await?;
+        response.rank(&coordinates, options);
+        Ok(response)
+    }
+}
+
+// ========== distance ==========
+impl GeocodioProxy {
+    /// Calculate the distance from one origin to each of the destinations.
+    /// 
+    /// Origins and destinations can be [`Coordinates`] or [`AddressParams`]. When
+    /// [`DistanceOptions::offline_fallback`] is set and the API can't be reached, 
+    /// straight-line distances are calculated locally as long as every location is
+    /// given as [`Coordinates`].
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// use geocodio_lib_rust::{request::{address::{AddressParams, Coordinates}, distance::{DistanceMode, DistanceOptions}}, GeocodioProxy};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let geocodio = GeocodioProxy::new().unwrap();
+    ///     let origin = Coordinates { latitude: 29.950888, longitude: -90.081025 };
+    ///     let destinations = vec![
+    ///         Coordinates { latitude: 40.81352, longitude: -74.074333 }.into(),
+    ///         AddressParams::String("1 AT&T Way, Arlington, TX 76011".to_string()).into(),
+    ///     ];
+    ///     let options = DistanceOptions::new().mode(DistanceMode::Driving);
+    /// 
+    ///     let response = geocodio
+    ///         .distance(origin, destinations, &options)
+    ///         .await
+    ///         .unwrap();
+    ///     for destination in response.destinations {
+    ///         println!("{:?}: {} miles", destination.query, destination.distance_miles);
+    ///     }
+    /// }
+    /// ```
+    pub async fn distance(&self, origin: impl Into<DistanceLocation>, destinations: Vec<DistanceLocation>, options: &DistanceOptions) -> Result<DistanceResponse, Error> {
+        let origin = origin.into();
+        origin.validate()?;
+        destinations.iter().try_for_each(DistanceLocation::validate)?;
+        let mut params = vec![
+            ("origin", origin.to_string()),
+            ("mode", options.mode.as_str().to_string()),
+        ];
+        params.extend(destinations.iter().map(|destination| ("destinations[]", destination.to_string())));
+        let query = &options.request_options();
+        let endpoint = "distance";
+        let result: Result<DistanceResponse, Error> = async {
+            single_fetch!(self, endpoint, params, destinations.len(), query, DistanceResponse)
+        }.await;
+
+        match result {
+            Err(err) if options.offline_fallback && is_offline(&err) => {
+                match (origin.coordinates(), all_coordinates(&destinations)) {
+                    (Some(origin), Some(destinations)) => Ok(DistanceResponse::straight_line(origin, &destinations)),
+                    _ => Err(err),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Calculate the distance from every origin to every destination.
+    pub async fn distance_matrix(&self, origins: Vec<DistanceLocation>, destinations: Vec<DistanceLocation>, options: &DistanceOptions) -> Result<DistanceMatrixResponse, Error> {
+        origins.iter().chain(&destinations).try_for_each(DistanceLocation::validate)?;
+        let body = serde_json::json!({
+            "origins": origins.iter().map(|origin| origin.to_string()).collect::<Vec<String>>(),
+            "destinations": destinations.iter().map(|destination| destination.to_string()).collect::<Vec<String>>(),
+            "mode": options.mode.as_str(),
+        });
+        let items = origins.len() * destinations.len();
+        let result = matrix_fetch(self, "distance-matrix", &body, items, &options.request_options()).await;
+
+        match result {
+            Err(err) if options.offline_fallback && is_offline(&err) => {
+                match (all_coordinates(&origins), all_coordinates(&destinations)) {
+                    (Some(origins), Some(destinations)) => Ok(DistanceMatrixResponse::straight_line(&origins, &destinations)),
+                    _ => Err(err),
+                }
+            }
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    request::{address::AddressInput, parser::ParsedAddress, region::{CaProvince, UsState}},
+    response::address::{Address, AddressComponents},
+};
+
+/// Components at least this similar count as [`ComponentMatch::Partial`].
+const PARTIAL_SIMILARITY: f64 = 0.8;
+
+/// How much each component contributes to [`MatchReport::score`].
+const NUMBER_WEIGHT: f64 = 0.25;
+const STREET_WEIGHT: f64 = 0.3;
+const SECONDARY_WEIGHT: f64 = 0.05;
+const CITY_WEIGHT: f64 = 0.15;
+const STATE_WEIGHT: f64 = 0.1;
+const POSTAL_CODE_WEIGHT: f64 = 0.15;
+
+/// How one component of the input compares with the result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComponentMatch {
+    /// Equal after normalization.
+    Match,
+    /// Close but not equal, e.g. a typo, with the similarity from 0 to 1.
+    Partial(f64),
+    /// Different, with the similarity from 0 to 1.
+    Mismatch(f64),
+    /// Given in the input but missing from the result.
+    Missing,
+    /// Not given in the input, so not compared.
+    NotGiven,
+}
+
+impl ComponentMatch {
+    /// Whether the component matched exactly or closely.
+    pub fn is_match(&self) -> bool {
+        matches!(self, ComponentMatch::Match | ComponentMatch::Partial(_))
+    }
+
+    /// Similarity from 0 to 1, or `None` if the component wasn't compared.
+    pub fn similarity(&self) -> Option<f64> {
+        match self {
+            ComponentMatch::Match => Some(1.0),
+            ComponentMatch::Partial(similarity) | ComponentMatch::Mismatch(similarity) => Some(*similarity),
+            ComponentMatch::Missing => Some(0.0),
+            ComponentMatch::NotGiven => None,
+        }
+    }
+}
+
+/// What to do with a result given its [`MatchReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchDecision {
+    Accept,
+    Review,
+    Reject,
+}
+
+/// Score and accuracy cut-offs for [`MatchReport::decide`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchThresholds {
+    /// Minimum score to accept a result.
+    pub accept: f64,
+    /// Minimum score to review a result rather than reject it.
+    pub review: f64,
+    /// Results with a lower Geocodio `accuracy` are reviewed rather than accepted.
+    pub min_accuracy: f64,
+}
+
+impl Default for MatchThresholds {
+    fn default() -> Self {
+        MatchThresholds { accept: 0.9, review: 0.7, min_accuracy: 0.8 }
+    }
+}
+
+/// A component by component comparison of an [`AddressInput`] with a result.
+///
+/// The street line of the input is parsed with [`ParsedAddress::parse_street_line`] so that suffixes,
+/// directionals and unit designators compare in their USPS forms, and states and
+/// provinces compare by code, so "1109 North Highland Street" matches "1109 N Highland St".
+///
+/// ```rust
+/// use geocodio_lib_rust::{
+///     matching::{ComponentMatch, MatchDecision, MatchReport},
+///     request::address::AddressInput,
+///     response::address::AddressComponents,
+/// };
+///
+/// let input = AddressInput::new()
+///     .line_1("1109 North Highland Street")
+///     .city("Arlington")
+///     .state("Virginia")
+///     .postal_code("22201");
+/// let components = AddressComponents {
+///     number: Some("1109".to_string()),
+///     formatted_street: Some("N Highland St".to_string()),
+///     city: Some("Arlington".to_string()),
+///     state: Some("VA".to_string()),
+///     zip: Some("22201".to_string()),
+///     ..Default::default()
+/// };
+/// let report = MatchReport::compare(&input, &components);
+/// assert_eq!(report.street, ComponentMatch::Match);
+/// assert_eq!(report.secondary, ComponentMatch::NotGiven);
+/// assert_eq!(report.score, 1.0);
+/// assert_eq!(report.decision(), MatchDecision::Accept);
+///
+/// let input = AddressInput::new().line_1("4 Oak St").state("Ontario");
+/// let components = AddressComponents { state: Some("ON".to_string()), ..Default::default() };
+/// assert_eq!(MatchReport::compare(&input, &components).state, ComponentMatch::Match);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchReport {
+    pub number: ComponentMatch,
+    /// The street name with its directionals and suffix.
+    pub street: ComponentMatch,
+    /// The unit number, e.g. the "4" of "Apt 4".
+    pub secondary: ComponentMatch,
+    pub city: ComponentMatch,
+    pub state: ComponentMatch,
+    pub postal_code: ComponentMatch,
+    /// Weighted similarity of the compared components from 0 to 1, or 0 if nothing
+    /// could be compared.
+    pub score: f64,
+    /// The result's Geocodio accuracy, when comparing against an [`Address`].
+    pub accuracy: Option<f64>,
+}
+
+impl MatchReport {
+    /// Compare `input` with the components of a result.
+    pub fn compare(input: &AddressInput, components: &AddressComponents) -> Self {
+        let street_line = [&input.line_1, &input.line_2]
+            .iter()
+            .filter_map(|line| line.as_deref())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parsed = ParsedAddress::parse_street_line(&street_line).unwrap_or_default();
+
+        let result_street = components.formatted_street.clone().or_else(|| {
+            let parts = [&components.pre_directional, &components.street, &components.suffix, &components.post_directional];
+            let street = parts.iter().filter_map(|part| part.as_deref()).collect::<Vec<_>>().join(" ");
+            Some(street).filter(|street| !street.is_empty())
+        });
+
+        let number = compare_text(parsed.number.as_deref(), components.number.as_deref());
+        let street = compare_text(parsed.formatted_street().as_deref(), result_street.as_deref());
+        let secondary = compare_text(parsed.secondary_number.as_deref(), components.secondary_number.as_deref());
+        let city = compare_text(input.city.as_deref(), components.city.as_deref());
+        let state = compare_state(input.state.as_deref(), components.state.as_deref());
+        let postal_code = compare_text(
+            input.postal_code.as_deref().map(zip5),
+            components.zip.as_deref().map(zip5),
+        );
+
+        let weighted = [
+            (number, NUMBER_WEIGHT),
+            (street, STREET_WEIGHT),
+            (secondary, SECONDARY_WEIGHT),
+            (city, CITY_WEIGHT),
+            (state, STATE_WEIGHT),
+            (postal_code, POSTAL_CODE_WEIGHT),
+        ];
+        let (total, weights) = weighted
+            .iter()
+            .filter_map(|(component, weight)| component.similarity().map(|similarity| (similarity * weight, weight)))
+            .fold((0.0, 0.0), |(total, weights), (score, weight)| (total + score, weights + weight));
+        let score = if weights > 0.0 { total / weights } else { 0.0 };
+
+        MatchReport { number, street, secondary, city, state, postal_code, score, accuracy: None }
+    }
+
+    /// [`MatchReport::decide`] with the default [`MatchThresholds`].
+    pub fn decision(&self) -> MatchDecision {
+        self.decide(&MatchThresholds::default())
+    }
+
+    /// Accept, review or reject the result.
+    ///
+    /// A different house number is always rejected since it's a different place. Otherwise
+    /// the score is checked against the thresholds, and a result that would be accepted
+    /// is reviewed instead when its accuracy is below `min_accuracy`.
+    pub fn decide(&self, thresholds: &MatchThresholds) -> MatchDecision {
+        if let ComponentMatch::Mismatch(_) = self.number {
+            return MatchDecision::Reject;
+        }
+        if self.score >= thresholds.accept {
+            match self.accuracy {
+                Some(accuracy) if accuracy < thresholds.min_accuracy => MatchDecision::Review,
+                _ => MatchDecision::Accept,
+            }
+        } else if self.score >= thresholds.review {
+            MatchDecision::Review
+        } else {
+            MatchDecision::Reject
+        }
+    }
+}
+
+impl Address {
+    /// Compare `input` with this result, see [`MatchReport`].
+    pub fn match_report(&self, input: &AddressInput) -> MatchReport {
+        MatchReport {
+            accuracy: Some(self.accuracy),
+            ..MatchReport::compare(input, &self.address_components)
+        }
+    }
+}
+
+fn compare_text(input: Option<&str>, result: Option<&str>) -> ComponentMatch {
+    let input = input.map(normalize).filter(|input| !input.is_empty());
+    let result = result.map(normalize).filter(|result| !result.is_empty());
+    match (input, result) {
+        (None, _) => ComponentMatch::NotGiven,
+        (Some(_), None) => ComponentMatch::Missing,
+        (Some(input), Some(result)) if input == result => ComponentMatch::Match,
+        (Some(input), Some(result)) => {
+            let similarity = similarity(&input, &result);
+            if similarity >= PARTIAL_SIMILARITY {
+                ComponentMatch::Partial(similarity)
+            } else {
+                ComponentMatch::Mismatch(similarity)
+            }
+        }
+    }
+}
+
+/// States and provinces compare by code so that full names match abbreviations.
+fn compare_state(input: Option<&str>, result: Option<&str>) -> ComponentMatch {
+    let code = |state: &str| {
+        state
+            .parse::<UsState>()
+            .map(|state| state.code())
+            .or_else(|_| state.parse::<CaProvince>().map(|province| province.code()))
+            .map_or_else(|_| state.to_string(), str::to_string)
+    };
+    compare_text(input.map(code).as_deref(), result.map(code).as_deref())
+}
+
+fn zip5(postal_code: &str) -> &str {
+    postal_code.split('-').next().unwrap_or(postal_code)
+}
+
+/// Uppercase letters and digits with single spaces between words.
+fn normalize(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// One minus the Levenshtein distance divided by the length of the longer string.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
@@ -0,0 +1,84 @@
+use std::{fmt, sync::Arc};
+
+/// A response body as received from the API, or as returned by a [`Middleware`] in
+/// place of calling it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawResponse {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+impl RawResponse {
+    /// A `200 OK` response with `body`, e.g. one read from a cache.
+    pub fn ok(body: impl Into<String>) -> Self {
+        RawResponse { status: reqwest::StatusCode::OK, body: body.into() }
+    }
+}
+
+/// Hooks run around every call to the API, added with [`GeocodioProxy::with_middleware`](crate::GeocodioProxy::with_middleware).
+///
+/// Middleware see the request before the API key is added, so the key can't leak into
+/// audit logs. Both hooks have default implementations that do nothing.
+///
+/// ```rust
+/// use std::{collections::HashMap, sync::Mutex};
+///
+/// use geocodio_lib_rust::middleware::{Middleware, RawResponse};
+///
+/// /// Answer repeated requests from memory.
+/// #[derive(Default)]
+/// struct Cache(Mutex<HashMap<String, RawResponse>>);
+///
+/// impl Middleware for Cache {
+///     fn before_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+///         self.0.lock().unwrap().get(request.url().as_str()).cloned()
+///     }
+///
+///     fn after_response(&self, request: &reqwest::Request, response: &RawResponse) {
+///         if response.status.is_success() {
+///             self.0.lock().unwrap().insert(request.url().to_string(), response.clone());
+///         }
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Inspect or change the outgoing request, e.g. to add headers. Returning a
+    /// response skips the API, and the later middleware, and uses it instead. Responses
+    /// returned here don't count towards [`usage`](crate::GeocodioProxy::usage).
+    fn before_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        let _ = request;
+        None
+    }
+
+    /// Observe the response the API returned for `request`. Responses from rejected
+    /// keys that were retried with the next key aren't passed here.
+    fn after_response(&self, request: &reqwest::Request, response: &RawResponse) {
+        let _ = (request, response);
+    }
+}
+
+/// The middleware of a proxy, run in the order they were added.
+#[derive(Default, Clone)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.0.push(middleware);
+    }
+
+    pub fn before_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+        self.0.iter().find_map(|middleware| middleware.before_request(request))
+    }
+
+    pub fn after_response(&self, request: &reqwest::Request, response: &RawResponse) {
+        for middleware in &self.0 {
+            middleware.after_response(request, response);
+        }
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiddlewareChain({} middleware)", self.0.len())
+    }
+}
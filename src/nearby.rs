@@ -0,0 +1,182 @@
+use rstar::{primitives::GeomWithData, RTree};
+
+use crate::{
+    errors::Error,
+    request::{address::Coordinates, coordinates::{COORDINATE_PRECISION, EARTH_RADIUS_KM}, distance::{DistanceLocation, DistanceMode, DistanceOptions}},
+    response::{address::Address, distance::DistanceDestination},
+    GeocodioProxy,
+};
+
+/// Points are indexed as unit vectors so that straight-line (chord) distance in the
+/// tree increases with great-circle distance on the surface.
+type IndexedPoint = GeomWithData<[f64; 3], usize>;
+
+/// An in-memory spatial index over geocoded [`Address`] records.
+///
+/// Answers "k nearest to these coordinates" and "everything within this radius"
+/// without calling the API. Use [`AddressIndex::nearest_by_driving`] to rank the
+/// closest candidates by driving distance instead.
+///
+/// ```rust
+/// use geocodio_lib_rust::{nearby::AddressIndex, request::address::Coordinates, response::address::Address};
+///
+/// let stores = vec![
+///     Address { formatted_address: "New Orleans".to_string(), location: Coordinates { latitude: 29.950888, longitude: -90.081025 }, ..Default::default() },
+///     Address { formatted_address: "East Rutherford".to_string(), location: Coordinates { latitude: 40.81352, longitude: -74.074333 }, ..Default::default() },
+///     Address { formatted_address: "Arlington".to_string(), location: Coordinates { latitude: 32.747778, longitude: -97.092778 }, ..Default::default() },
+/// ];
+/// let index = AddressIndex::new(stores);
+///
+/// let baton_rouge = Coordinates { latitude: 30.4515, longitude: -91.1871 };
+/// let nearest = index.nearest(&baton_rouge, 1);
+/// assert_eq!(nearest[0].address.formatted_address, "New Orleans");
+///
+/// let within = index.within_radius(&baton_rouge, 800.0);
+/// assert_eq!(within.len(), 2);
+/// ```
+pub struct AddressIndex {
+    tree: RTree<IndexedPoint>,
+    addresses: Vec<Address>,
+}
+
+/// An [`Address`] from an [`AddressIndex`] and how far it is from the query point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nearby<'a> {
+    pub address: &'a Address,
+    pub distance_km: f64,
+    /// Only available from [`AddressIndex::nearest_by_driving`].
+    pub duration_seconds: Option<f64>,
+}
+
+impl AddressIndex {
+    pub fn new(addresses: Vec<Address>) -> Self {
+        let points = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| IndexedPoint::new(unit_vector(&address.location), i))
+            .collect();
+        AddressIndex { tree: RTree::bulk_load(points), addresses }
+    }
+
+    pub fn addresses(&self) -> &[Address] {
+        &self.addresses
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// The `k` addresses closest to `point`, nearest first.
+    pub fn nearest(&self, point: &Coordinates, k: usize) -> Vec<Nearby<'_>> {
+        self.tree
+            .nearest_neighbor_iter(&unit_vector(point))
+            .take(k)
+            .map(|indexed| self.nearby(point, indexed.data))
+            .collect()
+    }
+
+    /// Every address within `radius_km` of `point`, nearest first.
+    pub fn within_radius(&self, point: &Coordinates, radius_km: f64) -> Vec<Nearby<'_>> {
+        let angle = (radius_km / EARTH_RADIUS_KM).clamp(0.0, std::f64::consts::PI);
+        let chord = 2.0 * (angle / 2.0).sin();
+        // Pad the search slightly so rounding never drops a point on the boundary.
+        let mut results = self
+            .tree
+            .locate_within_distance(unit_vector(point), (chord * (1.0 + 1e-9)).powi(2))
+            .map(|indexed| self.nearby(point, indexed.data))
+            .filter(|nearby| nearby.distance_km <= radius_km)
+            .collect::<Vec<_>>();
+        results.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        results
+    }
+
+    /// The `k` addresses closest to `point` by driving distance.
+    ///
+    /// The `candidates` closest addresses by straight-line distance (at least `k`) are
+    /// sent to [`GeocodioProxy::distance`] and re-ranked by the driving distance it returns.
+    /// Each candidate is sent with its position as an id, since the API may return the
+    /// destinations in a different order.
+    pub async fn nearest_by_driving(
+        &self,
+        geocodio: &GeocodioProxy,
+        point: &Coordinates,
+        k: usize,
+        candidates: usize,
+    ) -> Result<Vec<Nearby<'_>>, Error> {
+        let candidates = self.nearest(point, candidates.max(k));
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+        let destinations = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, nearby)| DistanceLocation::Identified(nearby.address.location.clone(), i.to_string()))
+            .collect();
+        let options = DistanceOptions::new().mode(DistanceMode::Driving);
+        let response = geocodio.distance(point.clone(), destinations, &options).await?;
+
+        let mut results = candidates
+            .into_iter()
+            .enumerate()
+            .map(|(i, nearby)| {
+                let destination = find_destination(&response.destinations, &i.to_string(), &nearby.address.location)
+                    .ok_or_else(|| Error::Api(format!(
+                        "the driving distance to {}",
+                        nearby.address.location.to_precision_string(COORDINATE_PRECISION)
+                    )))?;
+                Ok(Nearby {
+                    address: nearby.address,
+                    distance_km: destination.distance_km,
+                    duration_seconds: destination.duration_seconds,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        results.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        results.truncate(k);
+        Ok(results)
+    }
+
+    fn nearby(&self, point: &Coordinates, i: usize) -> Nearby<'_> {
+        let address = &self.addresses[i];
+        Nearby {
+            address,
+            distance_km: point.distance_km(&address.location),
+            duration_seconds: None,
+        }
+    }
+}
+
+impl FromIterator<Address> for AddressIndex {
+    fn from_iter<I: IntoIterator<Item = Address>>(iter: I) -> Self {
+        AddressIndex::new(iter.into_iter().collect())
+    }
+}
+
+/// The destination sent with `id`, or when the API didn't echo ids, the one at `location`.
+fn find_destination<'a>(destinations: &'a [DistanceDestination], id: &str, location: &Coordinates) -> Option<&'a DistanceDestination> {
+    destinations
+        .iter()
+        .find(|destination| destination.id.as_deref() == Some(id))
+        .or_else(|| {
+            destinations.iter().find(|destination| {
+                destination.id.is_none()
+                    && destination.coordinates().is_some_and(|coordinates| {
+                        coordinates.to_precision_string(COORDINATE_PRECISION) == location.to_precision_string(COORDINATE_PRECISION)
+                    })
+            })
+        })
+}
+
+fn unit_vector(coordinates: &Coordinates) -> [f64; 3] {
+    let latitude = coordinates.latitude.to_radians();
+    let longitude = coordinates.longitude.to_radians();
+    [
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    ]
+}
@@ -8,12 +8,82 @@ pub struct Coordinates {
     pub longitude: f64,
 }
 
+/// An address to geocode, as a single line, structured components, or an intersection.
+///
+/// Single line addresses convert from strings:
+///
+/// ```rust
+/// use geocodio_lib_rust::request::address::AddressParams;
+///
+/// let address: AddressParams = "1500 Sugar Bowl Dr, New Orleans, LA 70112".into();
+/// assert_eq!(address.to_string(), "1500 Sugar Bowl Dr, New Orleans, LA 70112");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub enum AddressParams {
     String(String),
     AddressInput(AddressInput),
+    Intersection(IntersectionInput),
 }
 
-#[derive(Serialize, Deserialize)]
+/// A structured address, built with [`AddressInput::new`] and its setters or from a
+/// `(street, city, state, postal_code)` tuple.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::address::AddressInput;
+///
+/// let address = AddressInput::new()
+///     .line_1("1500 Sugar Bowl Dr")
+///     .line_2("Suite 100")
+///     .city("New Orleans")
+///     .state("LA")
+///     .postal_code("70112");
+/// assert_eq!(address.to_string(), "1500 Sugar Bowl Dr Suite 100, New Orleans, LA 70112");
+///
+/// let from_tuple = AddressInput::from(("1500 Sugar Bowl Dr", "New Orleans", "LA", "70112"));
+/// assert_eq!(from_tuple.city.as_deref(), Some("New Orleans"));
+/// ```
+///
+/// Each component is sent as its own percent-encoded query parameter, with `line_2`
+/// appended to the street and empty components left out, so characters like `&` and
+/// `#` stay part of their value:
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+///
+/// use geocodio_lib_rust::{
+///     middleware::{Middleware, RawResponse},
+///     request::address::AddressInput,
+///     GeocodioProxy,
+/// };
+///
+/// struct RecordUrl(Arc<Mutex<Option<reqwest::Url>>>);
+///
+/// impl Middleware for RecordUrl {
+///     fn before_request(&self, request: &mut reqwest::Request) -> Option<RawResponse> {
+///         *self.0.lock().unwrap() = Some(request.url().clone());
+///         Some(RawResponse { status: reqwest::StatusCode::OK, body: "{}".to_string() })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let url = Arc::new(Mutex::new(None));
+///     let geocodio = GeocodioProxy::new_from_key("key".to_string())
+///         .unwrap()
+///         .with_middleware(RecordUrl(url.clone()));
+///     let address = AddressInput::new()
+///         .line_1("1 AT&T Way")
+///         .line_2("# 5")
+///         .city("Arlington")
+///         .state("TX")
+///         .postal_code("");
+///     let _ = geocodio.geocode(address.into(), None).await;
+///
+///     let url = url.lock().unwrap().take().unwrap();
+///     assert_eq!(url.query(), Some("street=1+AT%26T+Way+%23+5&city=Arlington&state=TX"));
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AddressInput {
     pub line_1: Option<String>,
     pub line_2: Option<String>,
@@ -21,4 +91,15 @@ pub struct AddressInput {
     pub state: Option<String>,
     pub country: Option<String>,
     pub postal_code: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Two cross streets and the locality they meet in, e.g. "Main St and 1st Ave, City, ST".
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntersectionInput {
+    pub street_1: String,
+    pub street_2: String,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+    pub postal_code: Option<String>,
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// A Geocodio API key.
+///
+/// The key is sent in the `Authorization` header rather than the URL, and is redacted
+/// when formatted with `Debug` so it doesn't end up in logs. Use [`ApiKey::expose`]
+/// when you really need the value.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::api_key::ApiKey;
+///
+/// let key = ApiKey::new("my-secret-key");
+/// assert_eq!(format!("{:?}", key), "ApiKey(<redacted>)");
+/// assert_eq!(key.expose(), "my-secret-key");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        ApiKey(key.into())
+    }
+
+    /// The API key in plain text.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        ApiKey(key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        ApiKey(key.to_string())
+    }
+}
@@ -0,0 +1,171 @@
+use std::{fmt, str::FromStr};
+
+use crate::errors::Error;
+
+use super::address::Coordinates;
+
+/// Mean radius of the Earth in kilometers, used for haversine distances.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+/// Number of kilometers in a statute mile.
+pub const KM_PER_MILE: f64 = 1.609344;
+/// Number of decimal places sent to the API, roughly 10 cm of precision.
+pub(crate) const COORDINATE_PRECISION: usize = 6;
+
+impl Coordinates {
+    /// Create validated [`Coordinates`].
+    ///
+    /// Latitude must be within -90 to 90 and longitude within -180 to 180, NaN and
+    /// infinite values are rejected.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::address::Coordinates;
+    ///
+    /// assert!(Coordinates::new(40.81352, -74.074333).is_ok());
+    /// assert!(Coordinates::new(200.0, -74.074333).is_err());
+    /// assert!(Coordinates::new(f64::NAN, 0.0).is_err());
+    /// ```
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, Error> {
+        let coordinates = Coordinates { latitude, longitude };
+        coordinates.validate()?;
+        Ok(coordinates)
+    }
+
+    /// Check that the latitude and longitude are finite and within range.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.latitude.is_finite() || !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(Error::InvalidCoordinates(format!(
+                "latitude {} must be between -90 and 90", self.latitude
+            )));
+        }
+        if !self.longitude.is_finite() || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(Error::InvalidCoordinates(format!(
+                "longitude {} must be between -180 and 180", self.longitude
+            )));
+        }
+        Ok(())
+    }
+
+    /// Great-circle distance to `other` in kilometers using the haversine formula.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::address::Coordinates;
+    ///
+    /// let superdome = Coordinates { latitude: 29.950888, longitude: -90.081025 };
+    /// let metlife = Coordinates { latitude: 40.813520, longitude: -74.074333 };
+    /// let distance = superdome.distance_km(&metlife);
+    /// assert!((distance - 1878.0).abs() < 5.0);
+    /// ```
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let lat_1 = self.latitude.to_radians();
+        let lat_2 = other.latitude.to_radians();
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lng = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat_1.cos() * lat_2.cos() * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    /// Great-circle distance to `other` in miles.
+    pub fn distance_miles(&self, other: &Coordinates) -> f64 {
+        self.distance_km(other) / KM_PER_MILE
+    }
+
+    /// Initial bearing towards `other` in degrees clockwise from north (0 to 360).
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::address::Coordinates;
+    ///
+    /// let origin = Coordinates { latitude: 0.0, longitude: 0.0 };
+    /// let east = Coordinates { latitude: 0.0, longitude: 1.0 };
+    /// assert!((origin.bearing_to(&east) - 90.0).abs() < 1e-9);
+    /// ```
+    pub fn bearing_to(&self, other: &Coordinates) -> f64 {
+        let lat_1 = self.latitude.to_radians();
+        let lat_2 = other.latitude.to_radians();
+        let d_lng = (other.longitude - self.longitude).to_radians();
+        let y = d_lng.sin() * lat_2.cos();
+        let x = lat_1.cos() * lat_2.sin() - lat_1.sin() * lat_2.cos() * d_lng.cos();
+        (y.atan2(x).to_degrees() + 360.0) % 360.0
+    }
+
+    /// Whether these coordinates fall inside `bounds`.
+    pub fn within(&self, bounds: &BoundingBox) -> bool {
+        bounds.contains(self)
+    }
+
+    /// Format as `lat,lng` with a fixed number of decimal places.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::address::Coordinates;
+    ///
+    /// let coordinates = Coordinates { latitude: 40.81352, longitude: -74.074333 };
+    /// assert_eq!(coordinates.to_precision_string(3), "40.814,-74.074");
+    /// ```
+    pub fn to_precision_string(&self, precision: usize) -> String {
+        format!("{:.*},{:.*}", precision, self.latitude, precision, self.longitude)
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.latitude, self.longitude)
+    }
+}
+
+/// Parse validated [`Coordinates`] from a `"lat,lng"` string.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::address::Coordinates;
+///
+/// let coordinates: Coordinates = "40.81352, -74.074333".parse().unwrap();
+/// assert_eq!(coordinates.longitude, -74.074333);
+/// assert!("40.81352".parse::<Coordinates>().is_err());
+/// ```
+impl FromStr for Coordinates {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidCoordinates(format!("expected \"lat,lng\", got \"{}\"", s));
+        let (latitude, longitude) = s.split_once(',').ok_or_else(invalid)?;
+        let latitude = latitude.trim().parse::<f64>().map_err(|_| invalid())?;
+        let longitude = longitude.trim().parse::<f64>().map_err(|_| invalid())?;
+        Coordinates::new(latitude, longitude)
+    }
+}
+
+/// A rectangular area bounded by its south-west and north-east corners.
+///
+/// Boxes that cross the antimeridian are supported by giving a south-west longitude
+/// greater than the north-east longitude.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub south_west: Coordinates,
+    pub north_east: Coordinates,
+}
+
+impl BoundingBox {
+    pub fn new(south_west: Coordinates, north_east: Coordinates) -> Self {
+        BoundingBox { south_west, north_east }
+    }
+
+    /// Whether `coordinates` fall inside the box, edges included.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::{address::Coordinates, coordinates::BoundingBox};
+    ///
+    /// let louisiana = BoundingBox::new(
+    ///     Coordinates { latitude: 28.9, longitude: -94.1 },
+    ///     Coordinates { latitude: 33.1, longitude: -88.8 },
+    /// );
+    /// assert!(louisiana.contains(&Coordinates { latitude: 29.950888, longitude: -90.081025 }));
+    /// assert!(!louisiana.contains(&Coordinates { latitude: 40.81352, longitude: -74.074333 }));
+    /// ```
+    pub fn contains(&self, coordinates: &Coordinates) -> bool {
+        let in_latitude = (self.south_west.latitude..=self.north_east.latitude).contains(&coordinates.latitude);
+        let in_longitude = if self.south_west.longitude <= self.north_east.longitude {
+            (self.south_west.longitude..=self.north_east.longitude).contains(&coordinates.longitude)
+        } else {
+            coordinates.longitude >= self.south_west.longitude || coordinates.longitude <= self.north_east.longitude
+        };
+        in_latitude && in_longitude
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+use super::{address::{AddressParams, Coordinates}, coordinates::COORDINATE_PRECISION, options::GeocodeOptions};
+
+/// An origin or destination for the distance endpoints, either [`Coordinates`] or an address.
+pub enum DistanceLocation {
+    Coordinates(Coordinates),
+    Address(AddressParams),
+    /// Coordinates with an id the API echoes back in
+    /// [`DistanceDestination::id`](crate::response::distance::DistanceDestination::id), so
+    /// results can be matched with their locations after the API has sorted them.
+    Identified(Coordinates, String),
+}
+
+impl DistanceLocation {
+    /// The coordinates of the location, if it wasn't given as an address.
+    pub fn coordinates(&self) -> Option<&Coordinates> {
+        match self {
+            DistanceLocation::Coordinates(coordinates) | DistanceLocation::Identified(coordinates, _) => Some(coordinates),
+            DistanceLocation::Address(_) => None,
+        }
+    }
+
+    /// Check the coordinates or address the same way geocoding and reverse geocoding do.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            DistanceLocation::Coordinates(coordinates) | DistanceLocation::Identified(coordinates, _) => coordinates.validate(),
+            DistanceLocation::Address(address) => Ok(address.validate()?),
+        }
+    }
+}
+
+/// The location as sent to the API, `lat,lng`, `lat,lng,id` or the address on a single line.
+impl fmt::Display for DistanceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceLocation::Coordinates(coordinates) => write!(f, "{}", coordinates.to_precision_string(COORDINATE_PRECISION)),
+            DistanceLocation::Address(address) => write!(f, "{}", address),
+            DistanceLocation::Identified(coordinates, id) => write!(f, "{},{}", coordinates.to_precision_string(COORDINATE_PRECISION), id),
+        }
+    }
+}
+
+impl From<Coordinates> for DistanceLocation {
+    fn from(coordinates: Coordinates) -> Self {
+        DistanceLocation::Coordinates(coordinates)
+    }
+}
+
+impl From<AddressParams> for DistanceLocation {
+    fn from(address: AddressParams) -> Self {
+        DistanceLocation::Address(address)
+    }
+}
+
+impl From<&str> for DistanceLocation {
+    fn from(address: &str) -> Self {
+        DistanceLocation::Address(address.into())
+    }
+}
+
+/// How distances are measured by the API.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DistanceMode {
+    /// Distance and duration along the road network.
+    Driving,
+    /// Great-circle distance, no duration.
+    #[default]
+    Straightline,
+}
+
+impl DistanceMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            DistanceMode::Driving => "driving",
+            DistanceMode::Straightline => "straightline",
+        }
+    }
+}
+
+/// Options for [`GeocodioProxy::distance`](crate::GeocodioProxy::distance) and
+/// [`GeocodioProxy::distance_matrix`](crate::GeocodioProxy::distance_matrix).
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DistanceOptions {
+    pub mode: DistanceMode,
+    /// When the API can't be reached and every location is given as [`Coordinates`],
+    /// calculate straight-line distances locally instead of returning an error.
+    pub offline_fallback: bool,
+    /// See [`GeocodeOptions::tenant`](super::options::GeocodeOptions::tenant).
+    pub tenant: Option<String>,
+    /// See [`GeocodeOptions::keep_raw`](super::options::GeocodeOptions::keep_raw).
+    pub keep_raw: bool,
+    /// See [`GeocodeOptions::lenient`](super::options::GeocodeOptions::lenient).
+    pub lenient: bool,
+}
+
+impl DistanceOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mode(mut self, mode: DistanceMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn offline_fallback(mut self, offline_fallback: bool) -> Self {
+        self.offline_fallback = offline_fallback;
+        self
+    }
+
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    pub fn keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    pub(crate) fn request_options(&self) -> GeocodeOptions {
+        GeocodeOptions { tenant: self.tenant.clone(), keep_raw: self.keep_raw, lenient: self.lenient, ..Default::default() }
+    }
+}
+
+/// Whether the request failed because the API couldn't be reached.
+pub(crate) fn is_offline(err: &Error) -> bool {
+    matches!(err, Error::Request(err) if err.is_connect() || err.is_timeout())
+}
+
+/// The coordinates of every location, or `None` if any of them is an address.
+pub(crate) fn all_coordinates(locations: &[DistanceLocation]) -> Option<Vec<Coordinates>> {
+    locations.iter().map(|location| location.coordinates().cloned()).collect()
+}
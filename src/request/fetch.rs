@@ -1,36 +1,172 @@
-use crate::{errors::Error, response::GeocodeBatchResponse, GeocodioProxy};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Path;
+
+use crate::{errors::Error, middleware::{MiddlewareChain, RawResponse}, response::{distance::DistanceMatrixResponse, GeocodeBatchResponse, WithMeta}, telemetry, usage::UsageTracker, GeocodioProxy};
+
+use super::{keys::KeyPool, lenient, options::GeocodeOptions, Served};
 
 const GEOCODIO_BASE_URL: &str = "https://api.geocod.io/v1.7/";
 
+/// The API version that added the distance endpoints, which aren't available under the
+/// version of [`GEOCODIO_BASE_URL`].
+pub(crate) const DISTANCE_API_VERSION: &str = "v1.9";
+
+/// Length of the excerpt of a response included in an [`Error::Decode`].
+const EXCERPT_LEN: usize = 120;
+
 #[macro_export]
 macro_rules! single_fetch {
-    ($data:ident, $endpoint:ident, $params:ident, $res:ty) => {{
-        let response = $data.request($endpoint, &$params).await?;
-        let json = response.json::<serde_json::Value>().await.unwrap();
-        let result = serde_json::from_value::<$res>(json);
-        match result {
-            Ok(geocode_response) => Ok(geocode_response),
-            Err(err) => Err(Error::BadInputData(err)),
-        }
+    ($data:ident, $endpoint:ident, $params:ident, $items:expr, $options:ident, $res:ty) => {{
+        let served = $data.request($endpoint, &$params, $items, $options).await?;
+        $crate::request::fetch::decode::<$res>(served).await
     }};
 }
 
-pub(crate) async fn batch_fetch(data: &GeocodioProxy, endpoint: String, params: Vec<String>) -> Result<GeocodeBatchResponse, Error> {
-    let res = data.request_batch(endpoint.as_str(), params).await?;
-    let json = res.json::<serde_json::Value>().await?;
-    let result = serde_json::from_value::<GeocodeBatchResponse>(json);
-    match result {
-        Ok(geocode_response) => Ok(geocode_response),
-        Err(err) => Err(Error::BadInputData(err)),
+pub(crate) async fn batch_fetch(data: &GeocodioProxy, endpoint: &str, params: Vec<String>, options: &GeocodeOptions) -> Result<GeocodeBatchResponse, Error> {
+    let served = data.request_batch(endpoint, params, options).await?;
+    decode::<GeocodeBatchResponse>(served).await
+}
+
+pub(crate) async fn matrix_fetch(data: &GeocodioProxy, endpoint: &str, body: &Value, items: usize, options: &GeocodeOptions) -> Result<DistanceMatrixResponse, Error> {
+    let served = data.request_json(endpoint, body, items, options).await?;
+    decode::<DistanceMatrixResponse>(served).await
+}
+
+/// Turn a response into `T`, or an [`Error::Status`] if the API returned an error.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    name = "geocodio.decode",
+    skip_all,
+    fields(endpoint = %served.endpoint, status = served.response.status.as_u16(), bytes = served.response.body.len())
+))]
+pub(crate) async fn decode<T: DeserializeOwned + WithMeta>(served: Served) -> Result<T, Error> {
+    let Served { response, served_by, endpoint, keep_raw, lenient } = served;
+    let result = decode_body::<T>(&endpoint, response, keep_raw, lenient).map(|mut decoded| {
+        for warning in decoded.api_warnings() {
+            telemetry::record_api_warning(&endpoint, warning);
+        }
+        decoded.meta_mut().served_by = served_by;
+        decoded
+    });
+    if let Err(error) = &result {
+        telemetry::record_error(&endpoint, error);
     }
+    result
 }
 
-pub(crate) fn proxy_new(api_key: String) -> Result<GeocodioProxy, Error> {
+/// The response as untyped json, or an [`Error::Status`] if the API returned an error.
+pub(crate) async fn decode_raw(served: Served) -> Result<Value, Error> {
+    let result = success_body(served.response).and_then(|body| {
+        serde_json::from_str(&body).map_err(|err| decode_error(&served.endpoint, &body, None, ".", err))
+    });
+    if let Err(error) = &result {
+        telemetry::record_error(&served.endpoint, error);
+    }
+    result
+}
+
+fn decode_body<T: DeserializeOwned + WithMeta>(endpoint: &str, response: RawResponse, keep_raw: bool, lenient: bool) -> Result<T, Error> {
+    let body = success_body(response)?;
+    let located = |raw: Option<&Value>, err: serde_path_to_error::Error<serde_json::Error>| {
+        let path = err.path().clone();
+        decode_error(endpoint, &body, raw.map(|raw| (raw, &path)), &path.to_string(), err.into_inner())
+    };
+    if !keep_raw && !lenient {
+        let mut deserializer = serde_json::Deserializer::from_str(&body);
+        let decoded = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| located(None, err))?;
+        deserializer.end().map_err(|err| decode_error(endpoint, &body, None, ".", err))?;
+        return Ok(decoded);
+    }
+
+    let raw = serde_json::from_str::<Value>(&body).map_err(|err| decode_error(endpoint, &body, None, ".", err))?;
+    let (mut decoded, warnings) = if lenient {
+        lenient::deserialize::<T>(raw.clone())
+    } else {
+        serde_path_to_error::deserialize::<_, T>(&raw).map(|decoded| (decoded, Vec::new()))
+    }
+    .map_err(|err| located(Some(&raw), err))?;
+    for warning in &warnings {
+        telemetry::record_decode_warning(endpoint, warning);
+    }
+    decoded.meta_mut().decode_warnings = warnings;
+    if keep_raw {
+        decoded.meta_mut().raw = Some(raw);
+    }
+    Ok(decoded)
+}
+
+/// An [`Error::Decode`] with an excerpt of the response around the value that failed, taken
+/// from `value` when the response was parsed into json first.
+fn decode_error(endpoint: &str, body: &str, value: Option<(&Value, &Path)>, path: &str, err: serde_json::Error) -> Error {
+    let excerpt = match value {
+        Some((raw, path)) => value_excerpt(raw, path).unwrap_or_else(|| truncate(body, 0)),
+        None => body_excerpt(body, err.line(), err.column()),
+    };
+    Error::Decode { endpoint: endpoint.to_string(), path: path.to_string(), message: err.to_string(), excerpt }
+}
+
+/// The json around `path`: the value itself if it's an object or array, otherwise the
+/// object or array holding it so the excerpt shows the field's name.
+fn value_excerpt(raw: &Value, path: &Path) -> Option<String> {
+    let value = lenient::value_at(raw, path)?;
+    let value = match value {
+        Value::Object(_) | Value::Array(_) => value,
+        _ => lenient::value_at(raw, path.iter().take(path.iter().len().saturating_sub(1)))?,
+    };
+    Some(truncate(&value.to_string(), 0))
+}
+
+/// The part of `body` around a line and column reported by serde_json, which are 0 when
+/// the error doesn't have a position.
+fn body_excerpt(body: &str, line: usize, column: usize) -> String {
+    if line == 0 {
+        return truncate(body, 0);
+    }
+    let line_start = body.split('\n').take(line - 1).map(|line| line.len() + 1).sum::<usize>();
+    let position = (line_start + column).min(body.len());
+    truncate(body, position.saturating_sub(EXCERPT_LEN / 2))
+}
+
+/// At most [`EXCERPT_LEN`] bytes of `text` from `start`, kept on character boundaries.
+fn truncate(text: &str, start: usize) -> String {
+    let boundary = |mut index: usize| {
+        index = index.min(text.len());
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    };
+    let (start, end) = (boundary(start), boundary(start + EXCERPT_LEN));
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < text.len() { "..." } else { "" };
+    format!("{prefix}{}{suffix}", &text[start..end])
+}
+
+/// The body of a successful response, or the API's error message as an [`Error::Status`].
+fn success_body(response: RawResponse) -> Result<String, Error> {
+    let RawResponse { status, body } = response;
+    if status.is_success() {
+        return Ok(body);
+    }
+    let message = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|json| json.get("error")?.as_str().map(str::to_string))
+        .unwrap_or(body);
+    Err(Error::Status { status: status.as_u16(), message })
+}
+
+pub(crate) fn proxy_new(keys: KeyPool) -> Result<GeocodioProxy, Error> {
+    if keys.is_empty() || (0..keys.len()).any(|i| keys.get(i).1.expose().trim().is_empty()) {
+        return Err(Error::MissingAPIKey);
+    }
+    keys.validate()?;
     let client = reqwest::Client::new();
 
     Ok(GeocodioProxy {
         client,
         base_url: reqwest::Url::parse(GEOCODIO_BASE_URL).unwrap(),
-        api_key,
+        keys,
+        usage: UsageTracker::default(),
+        middleware: MiddlewareChain::default(),
     })
-}
\ No newline at end of file
+}
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::errors::Error;
+
+use super::api_key::ApiKey;
+
+/// The label given to the key of a [`GeocodioProxy`](crate::GeocodioProxy) created from a single key.
+pub const DEFAULT_KEY_LABEL: &str = "default";
+
+/// How a [`KeyPool`] picks the key for each call.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStrategy {
+    /// Spread calls evenly across every key.
+    RoundRobin,
+    /// Always start with the first key, and only use the others when it fails.
+    #[default]
+    PrimaryFallback,
+    /// Use the keys assigned to the tenant in
+    /// [`GeocodeOptions::tenant`](super::options::GeocodeOptions::tenant). Calls without
+    /// a tenant behave like [`KeyStrategy::PrimaryFallback`].
+    PerTenant,
+}
+
+/// A set of labeled API keys used by a [`GeocodioProxy`](crate::GeocodioProxy).
+///
+/// When a key is rejected with a 403 (invalid key or quota exceeded), 402 or 429
+/// response, the call is retried with the next key the strategy allows. The label of
+/// the key that answered is reported in each response's `meta.served_by`.
+///
+/// ```rust
+/// use geocodio_lib_rust::{errors::Error, request::keys::{KeyPool, KeyStrategy}, GeocodioProxy};
+///
+/// let pool = KeyPool::new(KeyStrategy::PerTenant)
+///     .key("acme", "acme-api-key")
+///     .key("globex", "globex-api-key")
+///     .key("shared", "shared-api-key")
+///     .tenant("acme", "acme")
+///     .tenant("acme", "shared")
+///     .tenant("globex", "globex");
+/// let geocodio = GeocodioProxy::new_from_pool(pool).unwrap();
+///
+/// // Tenants can only be routed to keys that were added.
+/// let typo = KeyPool::new(KeyStrategy::PerTenant).key("acme", "acme-api-key").tenant("acme", "amce");
+/// assert!(matches!(GeocodioProxy::new_from_pool(typo), Err(Error::UnknownKeyLabel(label)) if label == "amce"));
+/// ```
+#[derive(Debug, Default)]
+pub struct KeyPool {
+    strategy: KeyStrategy,
+    keys: Vec<(String, ApiKey)>,
+    /// The labels of the keys assigned to each tenant.
+    tenants: HashMap<String, Vec<String>>,
+    next: AtomicUsize,
+}
+
+impl KeyPool {
+    pub fn new(strategy: KeyStrategy) -> Self {
+        KeyPool { strategy, ..Default::default() }
+    }
+
+    /// Add a key under `label`. Keys are tried in the order they're added.
+    pub fn key(mut self, label: impl Into<String>, key: impl Into<ApiKey>) -> Self {
+        self.keys.push((label.into(), key.into()));
+        self
+    }
+
+    /// Route calls for `tenant` to the key added as `label`. Call it more than once to
+    /// give a tenant fallback keys. Creating a [`GeocodioProxy`](crate::GeocodioProxy)
+    /// fails with [`Error::UnknownKeyLabel`] if no key was added as `label`.
+    pub fn tenant(mut self, tenant: impl Into<String>, label: &str) -> Self {
+        self.tenants.entry(tenant.into()).or_default().push(label.to_string());
+        self
+    }
+
+    pub fn strategy(&self) -> KeyStrategy {
+        self.strategy
+    }
+
+    /// The labels of every key, in the order they were added.
+    pub fn labels(&self) -> Vec<&str> {
+        self.keys.iter().map(|(label, _)| label.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The first key added to the pool.
+    pub fn primary(&self) -> Option<&ApiKey> {
+        self.keys.first().map(|(_, key)| key)
+    }
+
+    pub(crate) fn get(&self, i: usize) -> (&str, &ApiKey) {
+        let (label, key) = &self.keys[i];
+        (label, key)
+    }
+
+    /// Check that every label assigned to a tenant belongs to a key.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let unknown = self.tenants.values().flatten().find(|label| self.position(label).is_none());
+        match unknown {
+            Some(label) => Err(Error::UnknownKeyLabel(label.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn position(&self, label: &str) -> Option<usize> {
+        self.keys.iter().position(|(key_label, _)| key_label == label)
+    }
+
+    /// The keys to try for a call, in order.
+    pub(crate) fn order(&self, tenant: Option<&str>) -> Result<Vec<usize>, Error> {
+        let all = 0..self.keys.len();
+        match (self.strategy, tenant) {
+            (KeyStrategy::RoundRobin, _) => {
+                let start = self.next.fetch_add(1, Ordering::Relaxed) % self.keys.len();
+                Ok(all.map(|i| (start + i) % self.keys.len()).collect())
+            }
+            (KeyStrategy::PerTenant, Some(tenant)) => self
+                .tenants
+                .get(tenant)
+                .map(|labels| labels.iter().filter_map(|label| self.position(label)).collect())
+                .ok_or_else(|| Error::UnknownTenant(tenant.to_string())),
+            _ => Ok(all.collect()),
+        }
+    }
+}
+
+impl From<ApiKey> for KeyPool {
+    fn from(key: ApiKey) -> Self {
+        KeyPool::new(KeyStrategy::PrimaryFallback).key(DEFAULT_KEY_LABEL, key)
+    }
+}
+
+/// Whether a response means the key itself can't be used right now.
+pub(crate) fn is_key_failure(status: reqwest::StatusCode) -> bool {
+    matches!(
+        status,
+        reqwest::StatusCode::PAYMENT_REQUIRED | reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::TOO_MANY_REQUESTS
+    )
+}
@@ -0,0 +1,139 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+use crate::response::DecodeWarning;
+
+const INTEGERS: &[&str] = &["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+
+/// Fixes to try before giving up, so a response that keeps failing can't loop forever.
+const MAX_FIXES: usize = 100;
+
+/// Deserialize `value`, coercing values of the wrong type and filling in missing
+/// fields until it fits `T`. Every change is returned as a [`DecodeWarning`].
+///
+/// Numbers sent as strings are parsed, `null`s become empty strings, zeros, `false`
+/// or empty lists depending on what was expected, single values become one item lists,
+/// and missing fields are added as `null` and then coerced the same way.
+pub(crate) fn deserialize<T: DeserializeOwned>(mut value: Value) -> Result<(T, Vec<DecodeWarning>), serde_path_to_error::Error<serde_json::Error>> {
+    let mut warnings = Vec::new();
+    loop {
+        let err = match serde_path_to_error::deserialize::<_, T>(&value) {
+            Ok(decoded) => return Ok((decoded, warnings)),
+            Err(err) => err,
+        };
+        let message = err.inner().to_string();
+        if warnings.len() >= MAX_FIXES || !fix(&mut value, err.path(), &message) {
+            return Err(err);
+        }
+        warnings.push(DecodeWarning { path: err.path().to_string(), message });
+    }
+}
+
+/// The value at the end of `segments`, or `None` if they don't lead anywhere in `value`.
+pub(crate) fn value_at<'a, 'p>(value: &'a Value, segments: impl IntoIterator<Item = &'p serde_path_to_error::Segment>) -> Option<&'a Value> {
+    segments.into_iter().try_fold(value, |node, segment| match segment {
+        serde_path_to_error::Segment::Seq { index } => node.get(*index),
+        serde_path_to_error::Segment::Map { key } => node.get(key.as_str()),
+        _ => Some(node),
+    })
+}
+
+fn value_at_mut<'a>(value: &'a mut Value, path: &serde_path_to_error::Path) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |node, segment| match segment {
+        serde_path_to_error::Segment::Seq { index } => node.get_mut(*index),
+        serde_path_to_error::Segment::Map { key } => node.get_mut(key.as_str()),
+        _ => Some(node),
+    })
+}
+
+/// Change the value at `path` so the error described by `message` goes away.
+/// Returns `false` if there's nothing that can be done about it.
+fn fix(value: &mut Value, path: &serde_path_to_error::Path, message: &str) -> bool {
+    let Some(node) = value_at_mut(value, path) else {
+        return false;
+    };
+    if let Some(field) = message.strip_prefix("missing field `").and_then(|rest| rest.split('`').next()) {
+        return match node.as_object_mut() {
+            Some(object) if !object.contains_key(field) => {
+                object.insert(field.to_string(), Value::Null);
+                true
+            }
+            _ => false,
+        };
+    }
+    let Some(expected) = message.split(", expected ").nth(1) else {
+        return false;
+    };
+    // A value that coerces to itself, like an integer out of range, can't be fixed.
+    match coerce(node, expected) {
+        Some(coerced) if coerced != *node => {
+            *node = coerced;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// `node` converted to the type serde described as `expected`, e.g. "f64" or "a string".
+fn coerce(node: &Value, expected: &str) -> Option<Value> {
+    let expected = expected.split(" at line ").next().unwrap_or(expected);
+    if expected.contains("string") {
+        return match node {
+            Value::Null => Some(Value::String(String::new())),
+            Value::Number(number) => Some(Value::String(number.to_string())),
+            Value::Bool(boolean) => Some(Value::String(boolean.to_string())),
+            _ => None,
+        };
+    }
+    if expected.contains("bool") {
+        return match node {
+            Value::Null => Some(Value::Bool(false)),
+            Value::String(text) => match text.trim() {
+                "true" | "1" => Some(Value::Bool(true)),
+                "false" | "0" | "" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            Value::Number(number) => Some(Value::Bool(number.as_f64() != Some(0.0))),
+            _ => None,
+        };
+    }
+    if ["f32", "f64"].contains(&expected) {
+        return match node {
+            Value::Null => Some(Value::from(0.0)),
+            Value::String(text) => text.trim().parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+            _ => None,
+        };
+    }
+    if INTEGERS.contains(&expected) {
+        return match node {
+            Value::Null => Some(Value::from(0)),
+            Value::String(text) => integer(text.trim()),
+            Value::Number(number) => number.as_f64().filter(|value| value.fract() == 0.0).map(|value| Value::from(value as i64)),
+            _ => None,
+        };
+    }
+    if expected.contains("sequence") {
+        return match node {
+            Value::Null => Some(Value::Array(Vec::new())),
+            Value::Array(_) => None,
+            single => Some(Value::Array(vec![single.clone()])),
+        };
+    }
+    if expected.contains("struct") || expected.contains("map") {
+        return match node {
+            Value::Null => Some(Value::Object(Map::new())),
+            _ => None,
+        };
+    }
+    None
+}
+
+fn integer(text: &str) -> Option<Value> {
+    if let Ok(value) = text.parse::<i64>() {
+        return Some(Value::from(value));
+    }
+    text.parse::<f64>()
+        .ok()
+        .filter(|value| value.fract() == 0.0)
+        .map(|value| Value::from(value as i64))
+}
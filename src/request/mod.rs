@@ -1,28 +1,126 @@
-use crate::{errors::Error, GeocodioProxy};
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::{errors::Error, middleware::RawResponse, telemetry, usage::UsageTracker, GeocodioProxy};
+
+use self::{fetch::DISTANCE_API_VERSION, keys::is_key_failure, options::GeocodeOptions};
 
 pub mod fetch;
 pub mod address;
+pub mod api_key;
+pub mod coordinates;
+pub mod distance;
+pub mod keys;
+mod lenient;
+pub mod options;
+pub mod parser;
+pub mod region;
 pub mod utils;
+pub mod validation;
+
+/// A response along with the label of the key that was used to get it, `None` when
+/// a middleware answered instead of the API.
+pub(crate) struct Served {
+    pub response: RawResponse,
+    pub served_by: Option<String>,
+    pub endpoint: String,
+    /// Whether to keep the json in the decoded response's meta.
+    pub keep_raw: bool,
+    /// Whether to tolerate values of the wrong type while decoding.
+    pub lenient: bool,
+}
 
 impl GeocodioProxy {
     /// Helper function to wrap the request
-    pub(crate) async fn request(&self, endpoint: &str, params: &str) -> Result<reqwest::Response, Error> {
-        let mut url = self.base_url.join(endpoint).unwrap();
-        url.set_query(Some(params));
-        url.query_pairs_mut().append_pair("api_key", &self.api_key);
-        // println!("{}", url);
-        Ok(self.client.get(url).send().await.unwrap())
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "geocodio.request", skip_all, fields(endpoint = %endpoint, items = items)))]
+    pub(crate) async fn request(&self, endpoint: &str, params: &[(&str, String)], items: usize, options: &GeocodeOptions) -> Result<Served, Error> {
+        let mut url = self.endpoint_url(endpoint);
+        url.query_pairs_mut()
+            .extend_pairs(params)
+            .extend_pairs(options.query_pairs());
+        self.send(self.client.get(url), endpoint, items, options).await
     }
 
     /// Request Batch
-    pub(crate) async fn request_batch(&self, endpoint: &str, params: Vec<String>) -> Result<reqwest::Response, Error> {
-        let url = self.base_url.join(endpoint).unwrap();
-        let mut payload: Vec<String> = Vec::new();
-
-        params.iter().enumerate().for_each(|(_i, address)| {
-            payload.push(serde_json::Value::String(address.to_owned()).to_string());
-        });
-        let res = self.client.post(url).json(&payload).send().await?;
-        Ok(res)
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "geocodio.request_batch", skip_all, fields(endpoint = %endpoint, batch_size = params.len())))]
+    pub(crate) async fn request_batch(&self, endpoint: &str, params: Vec<String>, options: &GeocodeOptions) -> Result<Served, Error> {
+        let mut url = self.endpoint_url(endpoint);
+        url.query_pairs_mut().extend_pairs(options.query_pairs());
+        self.send(self.client.post(url).json(&params), endpoint, params.len(), options).await
+    }
+
+    /// Request with a JSON body
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "geocodio.request_json", skip_all, fields(endpoint = %endpoint, items = items)))]
+    pub(crate) async fn request_json<T: Serialize>(&self, endpoint: &str, body: &T, items: usize, options: &GeocodeOptions) -> Result<Served, Error> {
+        let mut url = self.endpoint_url(endpoint);
+        url.query_pairs_mut().extend_pairs(options.query_pairs());
+        self.send(self.client.post(url).json(body), endpoint, items, options).await
+    }
+
+    /// The url of `endpoint` under [`GeocodioProxy::base_url`]. The distance endpoints use
+    /// [`DISTANCE_API_VERSION`] in place of the base url's version.
+    fn endpoint_url(&self, endpoint: &str) -> reqwest::Url {
+        let mut base = self.base_url.clone();
+        let is_version = |segment: &str| segment.strip_prefix('v').is_some_and(|version| version.starts_with(|c: char| c.is_ascii_digit()));
+        let versioned = base
+            .path_segments()
+            .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+            .is_some_and(is_version);
+        if versioned && matches!(endpoint, "distance" | "distance-matrix") {
+            if let Ok(mut segments) = base.path_segments_mut() {
+                segments.pop_if_empty().pop().push(DISTANCE_API_VERSION).push("");
+            }
+        }
+        base.join(endpoint).unwrap()
+    }
+
+    /// Send the request with the keys picked by the [`keys::KeyPool`], moving on to
+    /// the next key when one is rejected, and count the lookups it used.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        name = "geocodio.send",
+        skip_all,
+        fields(endpoint = %endpoint, items = items, lookups)
+    ))]
+    async fn send(&self, request: reqwest::RequestBuilder, endpoint: &str, items: usize, options: &GeocodeOptions) -> Result<Served, Error> {
+        let result = self.send_with_keys(request, endpoint, items, options).await;
+        if let Err(error) = &result {
+            telemetry::record_error(endpoint, error);
+        }
+        result
+    }
+
+    async fn send_with_keys(&self, request: reqwest::RequestBuilder, endpoint: &str, items: usize, options: &GeocodeOptions) -> Result<Served, Error> {
+        let mut request = request.build()?;
+        if let Some(response) = self.middleware.before_request(&mut request) {
+            return Ok(Served { response, served_by: None, endpoint: endpoint.to_string(), keep_raw: options.keep_raw, lenient: options.lenient });
+        }
+        let lookups = UsageTracker::lookups(items, options);
+        let reservation = self.usage.reserve(lookups)?;
+        let order = self.keys.order(options.tenant.as_deref())?;
+        let last = order.len().saturating_sub(1);
+        for (attempt, i) in order.into_iter().enumerate() {
+            let (label, key) = self.keys.get(i);
+            let authorized = request.try_clone().expect("request bodies are always buffered");
+            let started = Instant::now();
+            let response = reqwest::RequestBuilder::from_parts(self.client.clone(), authorized)
+                .bearer_auth(key.expose())
+                .send()
+                .await?;
+            let status = response.status();
+            telemetry::record_response(endpoint, label, status.as_u16(), started.elapsed());
+            if attempt < last && is_key_failure(status) {
+                telemetry::record_retry(endpoint, label, status.as_u16());
+                continue;
+            }
+            let response = RawResponse { status, body: response.text().await? };
+            self.middleware.after_response(&request, &response);
+            if status.is_success() {
+                reservation.record(endpoint, items);
+                telemetry::record_lookups(endpoint, lookups);
+            }
+            return Ok(Served { response, served_by: Some(label.to_string()), endpoint: endpoint.to_string(), keep_raw: options.keep_raw, lenient: options.lenient });
+        }
+        Err(Error::MissingAPIKey)
     }
-}
\ No newline at end of file
+}
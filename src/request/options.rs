@@ -0,0 +1,121 @@
+/// Optional query parameters sent along with a request to the Geocodio API.
+///
+/// The same options can be used for single and batch geocoding as well as reverse
+/// geocoding. The `format=simple` parameter is set for you by
+/// [`GeocodioProxy::geocode_simple`](crate::GeocodioProxy::geocode_simple) and
+/// [`GeocodioProxy::reverse_geocode_simple`](crate::GeocodioProxy::reverse_geocode_simple),
+/// since it changes the shape of the response.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::options::GeocodeOptions;
+///
+/// let options = GeocodeOptions::new()
+///     .limit(1)
+///     .fields(&["timezone", "cd"]);
+/// assert_eq!(options.limit, Some(1));
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GeocodeOptions {
+    /// Maximum number of results to return for each query.
+    pub limit: Option<u32>,
+    /// [Field appends](https://www.geocod.io/docs/#fields) to request, e.g. `timezone` or `cd`.
+    pub fields: Vec<String>,
+    /// Any other query parameters that aren't covered by the fields above.
+    pub params: Vec<(String, String)>,
+    /// Picks the API keys used for the call when the [`KeyPool`](super::keys::KeyPool)
+    /// uses [`KeyStrategy::PerTenant`](super::keys::KeyStrategy::PerTenant). Not sent to the API.
+    pub tenant: Option<String>,
+    /// Keep the json of the response in its [`ResponseMeta::raw`](crate::response::ResponseMeta::raw).
+    /// Not sent to the API.
+    pub keep_raw: bool,
+    /// Tolerate values of the wrong type and missing fields in the response, recording
+    /// each in [`ResponseMeta::decode_warnings`](crate::response::ResponseMeta::decode_warnings).
+    /// Not sent to the API.
+    pub lenient: bool,
+    /// Drop reverse geocoding results further than this many kilometers from the
+    /// queried coordinates. Not sent to the API.
+    pub max_distance_km: Option<f64>,
+    /// Only keep reverse geocoding results with a `rooftop` or `point` accuracy type.
+    /// Not sent to the API.
+    pub rooftop_only: bool,
+}
+
+impl GeocodeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of results returned for each query.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request [field appends](https://www.geocod.io/docs/#fields) for each result.
+    pub fn fields(mut self, fields: &[&str]) -> Self {
+        self.fields = fields.iter().map(|field| field.to_string()).collect();
+        self
+    }
+
+    /// Add an arbitrary query parameter.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    /// Route the call to the API keys assigned to `tenant`.
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Keep the json each response was decoded from, including any fields this
+    /// version of the crate doesn't know about.
+    pub fn keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    /// Decode responses even when some values don't have the expected type, e.g. a
+    /// `null` where a string was expected or a number sent as a string.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Drop reverse geocoding results further than `max_distance_km` from the queried coordinates.
+    pub fn max_distance_km(mut self, max_distance_km: f64) -> Self {
+        self.max_distance_km = Some(max_distance_km);
+        self
+    }
+
+    /// Only keep reverse geocoding results that are a rooftop or point, not interpolated
+    /// along a street or the center of a place.
+    pub fn rooftop_only(mut self, rooftop_only: bool) -> Self {
+        self.rooftop_only = rooftop_only;
+        self
+    }
+
+    /// The options to send with a reverse geocoding call. The `limit` is applied after
+    /// filtering rather than by the API when results are filtered, so that filtered out
+    /// results don't take the place of ones that would have been kept.
+    pub(crate) fn reverse_request_options(&self) -> GeocodeOptions {
+        if self.max_distance_km.is_some() || self.rooftop_only {
+            GeocodeOptions { limit: None, ..self.clone() }
+        } else {
+            self.clone()
+        }
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        if let Some(limit) = self.limit {
+            pairs.push(("limit".to_string(), limit.to_string()));
+        }
+        if !self.fields.is_empty() {
+            pairs.push(("fields".to_string(), self.fields.join(",")));
+        }
+        pairs.extend(self.params.iter().cloned());
+        pairs
+    }
+}
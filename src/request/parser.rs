@@ -0,0 +1,611 @@
+use std::{fmt, str::FromStr};
+
+use crate::{errors::Error, response::address::AddressComponents};
+
+use super::{
+    address::{AddressInput, AddressParams},
+    region::{Country, UsState},
+};
+
+/// USPS street suffix abbreviations along with the spellings that normalize to them.
+const SUFFIXES: &[(&str, &[&str])] = &[
+    ("ALY", &["ALLEY", "ALLEE", "ALLY"]),
+    ("ANX", &["ANNEX", "ANEX", "ANNX"]),
+    ("ARC", &["ARCADE"]),
+    ("AVE", &["AVENUE", "AV", "AVEN", "AVENU", "AVN", "AVNUE"]),
+    ("BYU", &["BAYOU", "BAYOO"]),
+    ("BCH", &["BEACH"]),
+    ("BND", &["BEND"]),
+    ("BLF", &["BLUFF", "BLUF"]),
+    ("BTM", &["BOTTOM", "BOT", "BOTTM"]),
+    ("BLVD", &["BOULEVARD", "BOUL", "BOULV"]),
+    ("BR", &["BRANCH", "BRNCH"]),
+    ("BRG", &["BRIDGE", "BRDGE"]),
+    ("BRK", &["BROOK"]),
+    ("BG", &["BURG"]),
+    ("BYP", &["BYPASS", "BYPA", "BYPAS", "BYPS"]),
+    ("CP", &["CAMP", "CMP"]),
+    ("CYN", &["CANYON", "CANYN", "CNYN"]),
+    ("CPE", &["CAPE"]),
+    ("CSWY", &["CAUSEWAY", "CAUSWA"]),
+    ("CTR", &["CENTER", "CEN", "CENT", "CENTR", "CENTRE", "CNTER", "CNTR"]),
+    ("CIR", &["CIRCLE", "CIRC", "CIRCL", "CRCL", "CRCLE"]),
+    ("CLF", &["CLIFF"]),
+    ("CLB", &["CLUB"]),
+    ("CMN", &["COMMON"]),
+    ("COR", &["CORNER"]),
+    ("CRSE", &["COURSE"]),
+    ("CT", &["COURT"]),
+    ("CV", &["COVE"]),
+    ("CRK", &["CREEK"]),
+    ("CRES", &["CRESCENT", "CRSENT", "CRSNT"]),
+    ("XING", &["CROSSING", "CRSSNG"]),
+    ("DL", &["DALE"]),
+    ("DM", &["DAM"]),
+    ("DV", &["DIVIDE", "DIV", "DVD"]),
+    ("DR", &["DRIVE", "DRIV", "DRV"]),
+    ("EST", &["ESTATE"]),
+    ("ESTS", &["ESTATES"]),
+    ("EXPY", &["EXPRESSWAY", "EXP", "EXPR", "EXPRESS", "EXPW"]),
+    ("EXT", &["EXTENSION", "EXTN", "EXTNSN"]),
+    ("FLS", &["FALLS"]),
+    ("FRY", &["FERRY", "FRRY"]),
+    ("FLD", &["FIELD"]),
+    ("FLDS", &["FIELDS"]),
+    ("FLT", &["FLAT"]),
+    ("FRD", &["FORD"]),
+    ("FRST", &["FOREST", "FORESTS"]),
+    ("FRK", &["FORK", "FORG", "FORGE"]),
+    ("FT", &["FORT", "FRT"]),
+    ("FWY", &["FREEWAY", "FREEWY", "FRWAY", "FRWY"]),
+    ("GDN", &["GARDEN", "GARDN", "GRDEN", "GRDN"]),
+    ("GDNS", &["GARDENS", "GRDNS"]),
+    ("GTWY", &["GATEWAY", "GATEWY", "GATWAY", "GTWAY"]),
+    ("GLN", &["GLEN"]),
+    ("GRN", &["GREEN"]),
+    ("GRV", &["GROVE", "GROV"]),
+    ("HBR", &["HARBOR", "HARB", "HARBR", "HRBOR"]),
+    ("HVN", &["HAVEN"]),
+    ("HTS", &["HEIGHTS", "HT"]),
+    ("HWY", &["HIGHWAY", "HIGHWY", "HIWAY", "HIWY", "HWAY"]),
+    ("HL", &["HILL"]),
+    ("HLS", &["HILLS"]),
+    ("HOLW", &["HOLLOW", "HLLW", "HOLLOWS", "HOLWS"]),
+    ("INLT", &["INLET"]),
+    ("IS", &["ISLAND", "ISLND"]),
+    ("ISS", &["ISLANDS", "ISLNDS"]),
+    ("JCT", &["JUNCTION", "JCTION", "JCTN", "JUNCTN", "JUNCTON"]),
+    ("KY", &["KEY"]),
+    ("KNL", &["KNOLL", "KNOL"]),
+    ("LK", &["LAKE"]),
+    ("LKS", &["LAKES"]),
+    ("LNDG", &["LANDING", "LNDNG"]),
+    ("LN", &["LANE"]),
+    ("LGT", &["LIGHT"]),
+    ("LOOP", &["LOOPS"]),
+    ("MALL", &[]),
+    ("MNR", &["MANOR"]),
+    ("MDW", &["MEADOW"]),
+    ("MDWS", &["MEADOWS", "MEDOWS"]),
+    ("ML", &["MILL"]),
+    ("MSN", &["MISSION", "MISSN", "MSSN"]),
+    ("MTWY", &["MOTORWAY"]),
+    ("MT", &["MOUNT", "MNT"]),
+    ("MTN", &["MOUNTAIN", "MNTAIN", "MNTN", "MOUNTIN", "MTIN"]),
+    ("OPAS", &["OVERPASS"]),
+    ("PARK", &["PRK", "PARKS"]),
+    ("PKWY", &["PARKWAY", "PARKWY", "PKWAY", "PKY", "PARKWAYS", "PKWYS"]),
+    ("PASS", &[]),
+    ("PATH", &["PATHS"]),
+    ("PIKE", &["PIKES"]),
+    ("PNES", &["PINES"]),
+    ("PL", &["PLACE"]),
+    ("PLN", &["PLAIN"]),
+    ("PLNS", &["PLAINS"]),
+    ("PLZ", &["PLAZA", "PLZA"]),
+    ("PT", &["POINT"]),
+    ("PRT", &["PORT"]),
+    ("PR", &["PRAIRIE", "PRR"]),
+    ("RNCH", &["RANCH", "RANCHES", "RNCHS"]),
+    ("RDG", &["RIDGE", "RDGE"]),
+    ("RIV", &["RIVER", "RVR", "RIVR"]),
+    ("RD", &["ROAD"]),
+    ("RTE", &["ROUTE"]),
+    ("ROW", &[]),
+    ("RUN", &[]),
+    ("SHR", &["SHORE", "SHOAR"]),
+    ("SKWY", &["SKYWAY"]),
+    ("SPG", &["SPRING", "SPNG", "SPRNG"]),
+    ("SPGS", &["SPRINGS", "SPNGS", "SPRNGS"]),
+    ("SQ", &["SQUARE", "SQR", "SQRE", "SQU"]),
+    ("STA", &["STATION", "STATN", "STN"]),
+    ("ST", &["STREET", "STRT", "STR"]),
+    ("SMT", &["SUMMIT", "SUMIT", "SUMITT"]),
+    ("TER", &["TERRACE", "TERR"]),
+    ("TRCE", &["TRACE", "TRACES"]),
+    ("TRL", &["TRAIL", "TRAILS", "TRLS"]),
+    ("TUNL", &["TUNNEL", "TUNEL", "TUNLS", "TUNNELS", "TUNNL"]),
+    ("TPKE", &["TURNPIKE", "TRNPK", "TURNPK"]),
+    ("UN", &["UNION"]),
+    ("VLY", &["VALLEY", "VALLY", "VLLY"]),
+    ("VIA", &["VIADUCT", "VDCT", "VIADCT"]),
+    ("VW", &["VIEW"]),
+    ("VLG", &["VILLAGE", "VILL", "VILLAG", "VILLG", "VILLIAGE"]),
+    ("VL", &["VILLE"]),
+    ("VIS", &["VISTA", "VIST", "VST", "VSTA"]),
+    ("WALK", &["WALKS"]),
+    ("WAY", &["WY"]),
+    ("WLS", &["WELLS"]),
+];
+
+const DIRECTIONALS: &[(&str, &[&str])] = &[
+    ("N", &["NORTH"]),
+    ("S", &["SOUTH"]),
+    ("E", &["EAST"]),
+    ("W", &["WEST"]),
+    ("NE", &["NORTHEAST"]),
+    ("NW", &["NORTHWEST"]),
+    ("SE", &["SOUTHEAST"]),
+    ("SW", &["SOUTHWEST"]),
+];
+
+/// USPS secondary unit designators, the last field is whether a unit number follows.
+const UNITS: &[(&str, &[&str], bool)] = &[
+    ("APT", &["APARTMENT"], true),
+    ("BLDG", &["BUILDING"], true),
+    ("DEPT", &["DEPARTMENT"], true),
+    ("FL", &["FLOOR"], true),
+    ("HNGR", &["HANGAR"], true),
+    ("LOT", &[], true),
+    ("PIER", &[], true),
+    ("RM", &["ROOM"], true),
+    ("SLIP", &[], true),
+    ("SPC", &["SPACE"], true),
+    ("STE", &["SUITE"], true),
+    ("STOP", &[], true),
+    ("TRLR", &["TRAILER"], true),
+    ("UNIT", &[], true),
+    ("BSMT", &["BASEMENT"], false),
+    ("FRNT", &["FRONT"], false),
+    ("LBBY", &["LOBBY"], false),
+    ("LOWR", &["LOWER"], false),
+    ("OFC", &["OFFICE"], false),
+    ("PH", &["PENTHOUSE"], false),
+    ("REAR", &[], false),
+    ("SIDE", &[], false),
+    ("UPPR", &["UPPER"], false),
+];
+
+/// An address split into its components locally, without an API call.
+///
+/// Components are normalized the way Geocodio returns them: street suffixes, unit
+/// designators and directionals use USPS abbreviations, the state is a two letter
+/// code, and the street and city are title cased. Two spellings of the same address
+/// therefore parse to equal values, which makes [`ParsedAddress`] useful for
+/// deduplicating input before spending lookups.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::parser::ParsedAddress;
+///
+/// let address: ParsedAddress = "1109 North Highland Street, Suite 400, Arlington, Virginia 22201".parse().unwrap();
+/// assert_eq!(address.number.as_deref(), Some("1109"));
+/// assert_eq!(address.pre_directional.as_deref(), Some("N"));
+/// assert_eq!(address.street.as_deref(), Some("Highland"));
+/// assert_eq!(address.suffix.as_deref(), Some("St"));
+/// assert_eq!(address.secondary_unit.as_deref(), Some("Ste"));
+/// assert_eq!(address.secondary_number.as_deref(), Some("400"));
+/// assert_eq!(address.city.as_deref(), Some("Arlington"));
+/// assert_eq!(address.state.as_deref(), Some("VA"));
+/// assert_eq!(address.zip.as_deref(), Some("22201"));
+///
+/// let same: ParsedAddress = "1109 n highland st ste 400 arlington va 22201".parse().unwrap();
+/// assert_eq!(address, same);
+/// assert_eq!(same.to_string(), "1109 N Highland St Ste 400, Arlington, VA 22201");
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedAddress {
+    pub number: Option<String>,
+    pub pre_directional: Option<String>,
+    pub street: Option<String>,
+    pub suffix: Option<String>,
+    pub post_directional: Option<String>,
+    pub secondary_unit: Option<String>,
+    pub secondary_number: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub zip4: Option<String>,
+    pub country: Option<String>,
+    /// Words that couldn't be placed in a component, e.g. the `"Floor 3"` after the unit
+    /// of `"1 Park Ave Bldg 2 Floor 3"`, in the order they were given.
+    pub unparsed: Option<String>,
+}
+
+impl ParsedAddress {
+    /// Parse a single line address such as `"1109 N Highland St, Arlington, VA 22201"`.
+    ///
+    /// Commas are used to tell the street, city and state apart when present. Without
+    /// them the city is taken to be whatever follows the street suffix or unit, as long
+    /// as a state or ZIP follows it. Words that can't be placed, such as a city without
+    /// a state or ZIP, are kept in [`ParsedAddress::unparsed`].
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::parser::ParsedAddress;
+    ///
+    /// let address = ParsedAddress::parse("100 Main St Springfield").unwrap();
+    /// assert_eq!(address.street.as_deref(), Some("Main"));
+    /// assert_eq!(address.city, None);
+    /// assert_eq!(address.unparsed.as_deref(), Some("Springfield"));
+    /// assert_eq!(address.to_string(), "100 Main St Springfield");
+    ///
+    /// let address = ParsedAddress::parse("1 Park Ave Bldg 2 Floor 3").unwrap();
+    /// assert_eq!(address.street.as_deref(), Some("Park"));
+    /// assert_eq!(address.line_2().as_deref(), Some("Bldg 2"));
+    /// assert_eq!(address.unparsed.as_deref(), Some("Floor 3"));
+    ///
+    /// let address = ParsedAddress::parse("4 Oak St, Toronto, ON M5V 2T6").unwrap();
+    /// assert_eq!(address.street.as_deref(), Some("Oak"));
+    /// assert_eq!(address.city, None);
+    /// assert_eq!(address.unparsed.as_deref(), Some("Toronto, On M5v 2t6"));
+    ///
+    /// let address = ParsedAddress::parse("Main St and 1st Ave").unwrap();
+    /// assert_eq!(address.to_string(), "Main St And 1st Ave");
+    /// ```
+    pub fn parse(address: &str) -> Result<Self, Error> {
+        let mut segments = tokenize(address);
+        if segments.is_empty() {
+            return Err(Error::EmptyAddress);
+        }
+
+        let mut parsed = ParsedAddress::default();
+        let has_commas = segments.len() > 1;
+
+        if let Some(count) = tail_match(&segments, |words| words.parse() == Ok(Country::UnitedStates)) {
+            pop_tokens(&mut segments, count);
+            parsed.country = Some(Country::UnitedStates.into());
+        }
+        if let Some(last) = segments.last().and_then(|segment| segment.last()) {
+            if let Some((zip, zip4)) = parse_zip(last) {
+                parsed.zip = Some(zip);
+                parsed.zip4 = zip4;
+                pop_tokens(&mut segments, 1);
+            }
+        }
+        // Without commas or a ZIP, codes such as "CT" or "NE" more likely end the street.
+        let ambiguous = !has_commas && parsed.zip.is_none();
+        let state_match = tail_match(&segments, |words| {
+            words.parse::<UsState>().is_ok()
+                && !(ambiguous && (lookup(SUFFIXES, words).is_some() || lookup(DIRECTIONALS, words).is_some()))
+        });
+        if let Some(count) = state_match {
+            let tail = segments.last().unwrap();
+            let words = tail[tail.len() - count..].join(" ");
+            parsed.state = words.parse::<UsState>().ok().map(String::from);
+            pop_tokens(&mut segments, count);
+        }
+        let has_region = parsed.state.is_some() || parsed.zip.is_some();
+
+        // The first segment is the street, followed by any segments holding its unit.
+        let units = segments.iter().skip(1).take_while(|segment| is_unit(&segment[0])).count();
+        let mut others = segments.split_off((1 + units).min(segments.len()));
+        if has_region {
+            parsed.city = others.pop().map(|segment| title_case(&segment));
+        }
+
+        let street = segments.concat();
+        let rest = parsed.parse_street(&street, has_commas);
+        if !rest.is_empty() && parsed.city.is_none() && has_region {
+            parsed.city = Some(title_case(rest));
+        } else {
+            parsed.push_unparsed(&title_case(rest));
+        }
+        for segment in others {
+            parsed.push_unparsed(&title_case(&segment));
+        }
+
+        if parsed == ParsedAddress::default() {
+            return Err(Error::EmptyAddress);
+        }
+        Ok(parsed)
+    }
+
+    /// Parse a street line on its own, such as `"1109 N Highland St Apt 4"`.
+    ///
+    /// Unlike [`ParsedAddress::parse`] the end of the line isn't checked for a city, state
+    /// or ZIP, so streets named after a state keep every word.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::parser::ParsedAddress;
+    ///
+    /// let street = ParsedAddress::parse_street_line("12 Indiana").unwrap();
+    /// assert_eq!(street.street.as_deref(), Some("Indiana"));
+    /// assert_eq!(street.state, None);
+    /// ```
+    pub fn parse_street_line(line: &str) -> Result<Self, Error> {
+        let tokens = tokenize(line).concat();
+        let mut parsed = ParsedAddress::default();
+        let rest = parsed.parse_street(&tokens, true);
+        parsed.push_unparsed(&title_case(rest));
+        if parsed == ParsedAddress::default() {
+            return Err(Error::EmptyAddress);
+        }
+        Ok(parsed)
+    }
+
+    /// Add `words` to the end of [`ParsedAddress::unparsed`].
+    fn push_unparsed(&mut self, words: &str) {
+        if words.is_empty() {
+            return;
+        }
+        self.unparsed = Some(match self.unparsed.take() {
+            Some(unparsed) => format!("{}, {}", unparsed, words),
+            None => words.to_string(),
+        });
+    }
+
+    /// Fill in the street components from `tokens`, returning the tokens after the street.
+    fn parse_street<'a>(&mut self, tokens: &'a [String], has_commas: bool) -> &'a [String] {
+        let mut i = 0;
+        if tokens.first().is_some_and(|token| token.starts_with(|c: char| c.is_ascii_digit())) {
+            self.number = Some(tokens[0].clone());
+            i = 1;
+        }
+        if let Some(directional) = tokens.get(i).and_then(|token| lookup(DIRECTIONALS, token)) {
+            let remaining = tokens.len() - i - 1;
+            if remaining >= 2 || (remaining == 1 && lookup(SUFFIXES, &tokens[i + 1]).is_none()) {
+                self.pre_directional = Some(directional.to_string());
+                i += 1;
+            }
+        }
+
+        let unit = tokens[i..]
+            .iter()
+            .position(|token| is_unit(token))
+            .map(|position| i + position)
+            .filter(|&position| position > i);
+        let body = &tokens[i..unit.unwrap_or(tokens.len())];
+
+        // With commas the street stands alone so its suffix is the last one, without them
+        // the city may follow and contain suffix-like words such as "Park".
+        let mut suffixes = body
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, token)| lookup(SUFFIXES, token).is_some())
+            .map(|(position, _)| position);
+        let suffix = if has_commas || unit.is_some() { suffixes.next_back() } else { suffixes.next() };
+
+        let mut end = body.len();
+        match suffix {
+            Some(position) => {
+                self.suffix = lookup(SUFFIXES, &body[position]).map(title_case_word);
+                end = position + 1;
+                if let Some(directional) = body.get(end).and_then(|token| lookup(DIRECTIONALS, token)) {
+                    self.post_directional = Some(directional.to_string());
+                    end += 1;
+                }
+                if position > 0 {
+                    self.street = Some(title_case(&body[..position]));
+                }
+            }
+            None => {
+                let mut name = body;
+                if name.len() > 1 {
+                    if let Some(directional) = lookup(DIRECTIONALS, &name[name.len() - 1]) {
+                        self.post_directional = Some(directional.to_string());
+                        name = &name[..name.len() - 1];
+                    }
+                }
+                if !name.is_empty() {
+                    self.street = Some(title_case(name));
+                }
+            }
+        }
+
+        let Some(unit) = unit else {
+            return &body[end..];
+        };
+        self.push_unparsed(&title_case(&body[end..]));
+
+        let mut j = unit;
+        let token = &tokens[j];
+        j += 1;
+        if let Some(number) = token.strip_prefix('#') {
+            self.secondary_unit = Some("#".to_string());
+            if !number.is_empty() {
+                self.secondary_number = Some(number.to_string());
+            } else if let Some(number) = tokens.get(j) {
+                self.secondary_number = Some(number.trim_start_matches('#').to_string());
+                j += 1;
+            }
+        } else if let Some((designator, numbered)) = unit_designator(token) {
+            self.secondary_unit = Some(title_case_word(designator));
+            if numbered {
+                if let Some(number) = tokens.get(j) {
+                    self.secondary_number = Some(number.trim_start_matches('#').to_string());
+                    j += 1;
+                }
+            }
+        }
+        &tokens[j..]
+    }
+
+    /// The street line, e.g. `"1109 N Highland St"`.
+    pub fn line_1(&self) -> Option<String> {
+        join_words([
+            self.number.as_deref(),
+            self.formatted_street().as_deref(),
+        ])
+    }
+
+    /// The secondary unit line, e.g. `"Ste 400"` or `"#5"`.
+    pub fn line_2(&self) -> Option<String> {
+        match (self.secondary_unit.as_deref(), self.secondary_number.as_deref()) {
+            (Some("#"), Some(number)) => Some(format!("#{}", number)),
+            (unit, number) => join_words([unit, number]),
+        }
+    }
+
+    /// The street without the house number, e.g. `"N Highland St"`.
+    pub fn formatted_street(&self) -> Option<String> {
+        join_words([
+            self.pre_directional.as_deref(),
+            self.street.as_deref(),
+            self.suffix.as_deref(),
+            self.post_directional.as_deref(),
+        ])
+    }
+
+    /// The ZIP code including the ZIP+4 extension when one was given.
+    pub fn postal_code(&self) -> Option<String> {
+        match (&self.zip, &self.zip4) {
+            (Some(zip), Some(zip4)) => Some(format!("{}-{}", zip, zip4)),
+            (zip, _) => zip.clone(),
+        }
+    }
+}
+
+impl FromStr for ParsedAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParsedAddress::parse(s)
+    }
+}
+
+impl fmt::Display for ParsedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let street = join_words([self.line_1().as_deref(), self.line_2().as_deref(), self.unparsed.as_deref()]);
+        let region = join_words([self.state.as_deref(), self.postal_code().as_deref()]);
+        let parts = [street, self.city.clone(), region]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Unparsed words are sent after the unit on `line_2` so the API can place them.
+impl From<ParsedAddress> for AddressInput {
+    fn from(address: ParsedAddress) -> Self {
+        AddressInput {
+            line_1: address.line_1(),
+            line_2: join_words([address.line_2().as_deref(), address.unparsed.as_deref()]),
+            postal_code: address.postal_code(),
+            city: address.city,
+            state: address.state,
+            country: address.country,
+        }
+    }
+}
+
+impl From<ParsedAddress> for AddressParams {
+    fn from(address: ParsedAddress) -> Self {
+        AddressParams::AddressInput(address.into())
+    }
+}
+
+/// Components in the shape of Geocodio's `address_components`, for comparing local
+/// parses with [`Input`](crate::response::utils::Input) or result components.
+impl From<ParsedAddress> for AddressComponents {
+    fn from(address: ParsedAddress) -> Self {
+        AddressComponents {
+            formatted_street: address.formatted_street(),
+            number: address.number,
+            street: address.street,
+            suffix: address.suffix,
+            secondary_number: address.secondary_number,
+            secondary_unit: address.secondary_unit,
+            post_directional: address.post_directional,
+            city: address.city,
+            state: address.state,
+            zip: address.zip,
+            county: None,
+            country: address.country,
+            pre_directional: address.pre_directional,
+            prefix: None,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// The uppercase words of each comma separated segment, without periods.
+fn tokenize(address: &str) -> Vec<Vec<String>> {
+    address
+        .split(',')
+        .map(|segment| {
+            segment
+                .split_whitespace()
+                .map(|token| token.replace('.', "").to_uppercase())
+                .filter(|token| !token.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Number of trailing tokens in the last segment matching `matches`, trying up to four words.
+fn tail_match(segments: &[Vec<String>], matches: impl Fn(&str) -> bool) -> Option<usize> {
+    let tail = segments.last()?;
+    // A lone segment must keep at least one token for the street.
+    let max = if segments.len() > 1 { tail.len() } else { tail.len().saturating_sub(1) };
+    (1..=max.min(4))
+        .rev()
+        .find(|&count| matches(&tail[tail.len() - count..].join(" ")))
+}
+
+/// Remove `count` tokens from the end of the last segment, dropping it when emptied.
+fn pop_tokens(segments: &mut Vec<Vec<String>>, count: usize) {
+    if let Some(tail) = segments.last_mut() {
+        tail.truncate(tail.len() - count);
+        if tail.is_empty() {
+            segments.pop();
+        }
+    }
+}
+
+fn parse_zip(token: &str) -> Option<(String, Option<String>)> {
+    let digits = |value: &str, len: usize| value.len() == len && value.bytes().all(|b| b.is_ascii_digit());
+    match token.split_once('-') {
+        Some((zip, zip4)) if digits(zip, 5) && digits(zip4, 4) => Some((zip.to_string(), Some(zip4.to_string()))),
+        None if digits(token, 5) => Some((token.to_string(), None)),
+        None if digits(token, 9) => Some((token[..5].to_string(), Some(token[5..].to_string()))),
+        _ => None,
+    }
+}
+
+fn lookup(table: &[(&'static str, &[&str])], token: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(abbreviation, variants)| *abbreviation == token || variants.contains(&token))
+        .map(|(abbreviation, _)| *abbreviation)
+}
+
+/// Whether `token` starts a secondary unit, e.g. `"APT"` or `"#5"`.
+fn is_unit(token: &str) -> bool {
+    token.starts_with('#') || unit_designator(token).is_some()
+}
+
+fn unit_designator(token: &str) -> Option<(&'static str, bool)> {
+    UNITS
+        .iter()
+        .find(|(designator, variants, _)| *designator == token || variants.contains(&token))
+        .map(|(designator, _, numbered)| (*designator, *numbered))
+}
+
+fn title_case_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+fn title_case(words: &[String]) -> String {
+    words.iter().map(|word| title_case_word(word)).collect::<Vec<_>>().join(" ")
+}
+
+fn join_words<'a>(words: impl IntoIterator<Item = Option<&'a str>>) -> Option<String> {
+    let joined = words.into_iter().flatten().collect::<Vec<_>>().join(" ");
+    if joined.is_empty() { None } else { Some(joined) }
+}
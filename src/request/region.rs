@@ -0,0 +1,255 @@
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::ValidationError;
+
+/// Generates a region enum with its codes and names, parsing from either in any case
+/// and displaying and serializing as the code.
+macro_rules! regions {
+    ($(#[$meta:meta])* $region:ident, $all:literal, $(($variant:ident, $code:literal, $name:literal)),* $(,)?) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $region {
+            $($variant),*
+        }
+
+        impl $region {
+            #[doc = $all]
+            pub const ALL: &'static [$region] = &[$($region::$variant),*];
+
+            /// The two letter postal code, e.g. `"LA"` or `"QC"`.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $($region::$variant => $code),*
+                }
+            }
+
+            /// The full name, e.g. `"Louisiana"` or `"Quebec"`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($region::$variant => $name),*
+                }
+            }
+        }
+
+        impl FromStr for $region {
+            type Err = ValidationError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let region = normalize(s);
+                $region::ALL
+                    .iter()
+                    .find(|candidate| candidate.code() == region || candidate.name().to_uppercase() == region)
+                    .copied()
+                    .ok_or_else(|| ValidationError::State(s.trim().to_string()))
+            }
+        }
+
+        impl fmt::Display for $region {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.code())
+            }
+        }
+
+        /// The postal code, so it can be passed to [`AddressInput::state`](super::address::AddressInput::state).
+        impl From<$region> for String {
+            fn from(region: $region) -> Self {
+                region.code().to_string()
+            }
+        }
+
+        impl Serialize for $region {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.code())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $region {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+regions! {
+    /// A US state, the District of Columbia, or a US territory.
+    ///
+    /// Parses from USPS codes and full names in any case, and displays and
+    /// serializes as the two letter code Geocodio uses.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::region::UsState;
+    ///
+    /// assert_eq!("la".parse::<UsState>().unwrap(), UsState::Louisiana);
+    /// assert_eq!("District of Columbia".parse::<UsState>().unwrap().code(), "DC");
+    /// assert!("ZZ".parse::<UsState>().is_err());
+    /// ```
+    UsState,
+    "Every state, DC, and territory in alphabetical order of name.",
+    (Alabama, "AL", "Alabama"),
+    (Alaska, "AK", "Alaska"),
+    (AmericanSamoa, "AS", "American Samoa"),
+    (Arizona, "AZ", "Arizona"),
+    (Arkansas, "AR", "Arkansas"),
+    (California, "CA", "California"),
+    (Colorado, "CO", "Colorado"),
+    (Connecticut, "CT", "Connecticut"),
+    (Delaware, "DE", "Delaware"),
+    (DistrictOfColumbia, "DC", "District of Columbia"),
+    (Florida, "FL", "Florida"),
+    (Georgia, "GA", "Georgia"),
+    (Guam, "GU", "Guam"),
+    (Hawaii, "HI", "Hawaii"),
+    (Idaho, "ID", "Idaho"),
+    (Illinois, "IL", "Illinois"),
+    (Indiana, "IN", "Indiana"),
+    (Iowa, "IA", "Iowa"),
+    (Kansas, "KS", "Kansas"),
+    (Kentucky, "KY", "Kentucky"),
+    (Louisiana, "LA", "Louisiana"),
+    (Maine, "ME", "Maine"),
+    (Maryland, "MD", "Maryland"),
+    (Massachusetts, "MA", "Massachusetts"),
+    (Michigan, "MI", "Michigan"),
+    (Minnesota, "MN", "Minnesota"),
+    (Mississippi, "MS", "Mississippi"),
+    (Missouri, "MO", "Missouri"),
+    (Montana, "MT", "Montana"),
+    (Nebraska, "NE", "Nebraska"),
+    (Nevada, "NV", "Nevada"),
+    (NewHampshire, "NH", "New Hampshire"),
+    (NewJersey, "NJ", "New Jersey"),
+    (NewMexico, "NM", "New Mexico"),
+    (NewYork, "NY", "New York"),
+    (NorthCarolina, "NC", "North Carolina"),
+    (NorthDakota, "ND", "North Dakota"),
+    (NorthernMarianaIslands, "MP", "Northern Mariana Islands"),
+    (Ohio, "OH", "Ohio"),
+    (Oklahoma, "OK", "Oklahoma"),
+    (Oregon, "OR", "Oregon"),
+    (Pennsylvania, "PA", "Pennsylvania"),
+    (PuertoRico, "PR", "Puerto Rico"),
+    (RhodeIsland, "RI", "Rhode Island"),
+    (SouthCarolina, "SC", "South Carolina"),
+    (SouthDakota, "SD", "South Dakota"),
+    (Tennessee, "TN", "Tennessee"),
+    (Texas, "TX", "Texas"),
+    (Utah, "UT", "Utah"),
+    (Vermont, "VT", "Vermont"),
+    (VirginIslands, "VI", "Virgin Islands"),
+    (Virginia, "VA", "Virginia"),
+    (Washington, "WA", "Washington"),
+    (WestVirginia, "WV", "West Virginia"),
+    (Wisconsin, "WI", "Wisconsin"),
+    (Wyoming, "WY", "Wyoming"),
+}
+
+regions! {
+    /// A Canadian province or territory.
+    ///
+    /// Parses from Canada Post codes and full names in any case, and displays and
+    /// serializes as the two letter code.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::request::region::CaProvince;
+    ///
+    /// assert_eq!("on".parse::<CaProvince>().unwrap(), CaProvince::Ontario);
+    /// assert_eq!("British Columbia".parse::<CaProvince>().unwrap().code(), "BC");
+    /// assert!("ZZ".parse::<CaProvince>().is_err());
+    /// ```
+    CaProvince,
+    "Every province and territory in alphabetical order of name.",
+    (Alberta, "AB", "Alberta"),
+    (BritishColumbia, "BC", "British Columbia"),
+    (Manitoba, "MB", "Manitoba"),
+    (NewBrunswick, "NB", "New Brunswick"),
+    (NewfoundlandAndLabrador, "NL", "Newfoundland and Labrador"),
+    (NorthwestTerritories, "NT", "Northwest Territories"),
+    (NovaScotia, "NS", "Nova Scotia"),
+    (Nunavut, "NU", "Nunavut"),
+    (Ontario, "ON", "Ontario"),
+    (PrinceEdwardIsland, "PE", "Prince Edward Island"),
+    (Quebec, "QC", "Quebec"),
+    (Saskatchewan, "SK", "Saskatchewan"),
+    (Yukon, "YT", "Yukon"),
+}
+
+/// The countries Geocodio covers.
+///
+/// ```rust
+/// use geocodio_lib_rust::request::region::Country;
+///
+/// assert_eq!("USA".parse::<Country>().unwrap(), Country::UnitedStates);
+/// assert_eq!("canada".parse::<Country>().unwrap().code(), "CA");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Country {
+    UnitedStates,
+    Canada,
+}
+
+impl Country {
+    /// The ISO 3166 alpha-2 code, `"US"` or `"CA"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Country::UnitedStates => "US",
+            Country::Canada => "CA",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Country::UnitedStates => "United States",
+            Country::Canada => "Canada",
+        }
+    }
+}
+
+impl FromStr for Country {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize(s).as_str() {
+            "US" | "USA" | "UNITED STATES" | "UNITED STATES OF AMERICA" => Ok(Country::UnitedStates),
+            "CA" | "CAN" | "CANADA" => Ok(Country::Canada),
+            _ => Err(ValidationError::Country(s.trim().to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Country {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// The ISO code, so a [`Country`] can be passed to [`AddressInput::country`](super::address::AddressInput::country).
+impl From<Country> for String {
+    fn from(country: Country) -> Self {
+        country.code().to_string()
+    }
+}
+
+impl Serialize for Country {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Country {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Uppercase with periods removed and whitespace collapsed, so "d.c." and "New  York" match.
+fn normalize(value: &str) -> String {
+    value
+        .replace('.', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
@@ -1,59 +1,180 @@
-use super::address::AddressInput;
+use std::fmt;
+
+use super::address::{AddressInput, AddressParams, IntersectionInput};
+
+impl AddressParams {
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        match self {
+            AddressParams::String(address) => vec![("q", address.to_string())],
+            AddressParams::AddressInput(address) => address.query_pairs(),
+            AddressParams::Intersection(intersection) => vec![("q", intersection.to_string())],
+        }
+    }
+}
+
+/// The address on a single line, as sent in batches and to the distance endpoints.
+impl fmt::Display for AddressParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressParams::String(address) => write!(f, "{}", address),
+            AddressParams::AddressInput(address) => write!(f, "{}", address),
+            AddressParams::Intersection(intersection) => write!(f, "{}", intersection),
+        }
+    }
+}
+
+impl From<&str> for AddressParams {
+    fn from(address: &str) -> Self {
+        AddressParams::String(address.to_string())
+    }
+}
+
+impl From<String> for AddressParams {
+    fn from(address: String) -> Self {
+        AddressParams::String(address)
+    }
+}
+
+impl From<AddressInput> for AddressParams {
+    fn from(address: AddressInput) -> Self {
+        AddressParams::AddressInput(address)
+    }
+}
+
+impl From<IntersectionInput> for AddressParams {
+    fn from(intersection: IntersectionInput) -> Self {
+        AddressParams::Intersection(intersection)
+    }
+}
+
+/// A `(street, city, state, postal_code)` tuple.
+impl<S, C, T, P> From<(S, C, T, P)> for AddressParams
+where
+    S: Into<String>,
+    C: Into<String>,
+    T: Into<String>,
+    P: Into<String>,
+{
+    fn from(address: (S, C, T, P)) -> Self {
+        AddressParams::AddressInput(address.into())
+    }
+}
 
 impl AddressInput {
-    pub(crate) fn fmt_string(&self) -> String {
-        let binding = "".to_string();
-        let line_1 = match &self.line_1 {
-            Some(val) => val,
-            None => &binding,
-        };
-        let city = match &self.city {
-            Some(val) => val,
-            None => &binding,
-        };
-        let state = match &self.state {
-            Some(val) => val,
-            None => &binding,
-        };
-        let country = match &self.country {
-            Some(val) => val,
-            None => &binding,
-        };
-        let postal_code = match &self.postal_code {
-            Some(val) => val,
-            None => &binding,
-        };
-        format!(
-            "street={}&city={}&state={}&country={}&postal_code={}",
-            line_1, city, state, country, postal_code
-        )
-    }
-    
-    pub(crate) fn to_string(&self) -> String {
-        let binding = "".to_string();
-        let line_1 = match &self.line_1 {
-            Some(val) => val,
-            None => &binding,
-        };
-        let city = match &self.city {
-            Some(val) => val,
-            None => &binding,
-        };
-        let state = match &self.state {
-            Some(val) => val,
-            None => &binding,
-        };
-        let country = match &self.country {
-            Some(val) => val,
-            None => &binding,
-        };
-        let postal_code = match &self.postal_code {
-            Some(val) => val,
-            None => &binding,
+    /// Create an empty [`AddressInput`] to fill in with the setters below.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the street line, e.g. `"1500 Sugar Bowl Dr"`.
+    pub fn line_1(mut self, line_1: impl Into<String>) -> Self {
+        self.line_1 = Some(line_1.into());
+        self
+    }
+
+    /// Set the secondary line, e.g. `"Suite 100"`.
+    pub fn line_2(mut self, line_2: impl Into<String>) -> Self {
+        self.line_2 = Some(line_2.into());
+        self
+    }
+
+    pub fn city(mut self, city: impl Into<String>) -> Self {
+        self.city = Some(city.into());
+        self
+    }
+
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    pub fn country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    pub fn postal_code(mut self, postal_code: impl Into<String>) -> Self {
+        self.postal_code = Some(postal_code.into());
+        self
+    }
+
+    /// Structured address parameters, with `line_2` appended to the street and
+    /// empty components left out.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let street = [&self.line_1, &self.line_2]
+            .iter()
+            .filter_map(|line| non_empty(line))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut pairs = Vec::new();
+        if !street.is_empty() {
+            pairs.push(("street", street));
+        }
+        let components = [
+            ("city", &self.city),
+            ("state", &self.state),
+            ("postal_code", &self.postal_code),
+            ("country", &self.country),
+        ];
+        for (key, value) in components {
+            if let Some(value) = non_empty(value) {
+                pairs.push((key, value.to_string()));
+            }
+        }
+        pairs
+    }
+
+}
+
+/// `line_1 line_2, city, state postal_code, country`, leaving out empty components.
+impl fmt::Display for AddressInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |parts: &[&Option<String>], separator: &str| {
+            parts.iter().filter_map(|part| non_empty(part)).collect::<Vec<_>>().join(separator)
         };
-        format!(
-            "{} {}, {}, {}, {}",
-            line_1, city, state, country, postal_code
-        )
+        let parts = [
+            join(&[&self.line_1, &self.line_2], " "),
+            join(&[&self.city], ""),
+            join(&[&self.state, &self.postal_code], " "),
+            join(&[&self.country], ""),
+        ];
+        let parts = parts.iter().filter(|part| !part.is_empty()).map(String::as_str).collect::<Vec<_>>();
+        write!(f, "{}", parts.join(", "))
     }
-}
\ No newline at end of file
+}
+
+/// A `(street, city, state, postal_code)` tuple.
+impl<S, C, T, P> From<(S, C, T, P)> for AddressInput
+where
+    S: Into<String>,
+    C: Into<String>,
+    T: Into<String>,
+    P: Into<String>,
+{
+    fn from((street, city, state, postal_code): (S, C, T, P)) -> Self {
+        AddressInput::new()
+            .line_1(street)
+            .city(city)
+            .state(state)
+            .postal_code(postal_code)
+    }
+}
+
+impl fmt::Display for IntersectionInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} and {}", self.street_1, self.street_2)?;
+        let locality = [&self.city, &self.state, &self.postal_code, &self.country]
+            .iter()
+            .filter_map(|part| part.as_deref())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+        if !locality.is_empty() {
+            write!(f, ", {}", locality.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
@@ -0,0 +1,155 @@
+use crate::errors::ValidationError;
+
+use super::{
+    address::{AddressInput, AddressParams, IntersectionInput},
+    region::{CaProvince, Country, UsState},
+};
+
+/// Which country's rules apply to the state and postal code.
+enum Region {
+    UnitedStates,
+    Canada,
+    /// No country was given, Geocodio supports the US and Canada.
+    Unspecified,
+    Other,
+}
+
+impl Region {
+    fn of(country: &Option<String>) -> Self {
+        let Some(country) = non_blank(country) else {
+            return Region::Unspecified;
+        };
+        match country.parse() {
+            Ok(Country::UnitedStates) => Region::UnitedStates,
+            Ok(Country::Canada) => Region::Canada,
+            Err(_) => Region::Other,
+        }
+    }
+}
+
+impl AddressParams {
+    /// Check the address before it is sent, so that calls that can't succeed aren't
+    /// spent. Single address geocoding and batches run this automatically.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::{errors::ValidationError, request::address::{AddressInput, AddressParams}};
+    ///
+    /// let address = AddressParams::AddressInput(AddressInput {
+    ///     line_1: None,
+    ///     line_2: None,
+    ///     city: None,
+    ///     state: None,
+    ///     country: None,
+    ///     postal_code: None,
+    /// });
+    /// assert_eq!(address.validate(), Err(ValidationError::Empty));
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            AddressParams::String(address) if address.trim().is_empty() => Err(ValidationError::Empty),
+            AddressParams::String(_) => Ok(()),
+            AddressParams::AddressInput(address) => address.validate(),
+            AddressParams::Intersection(intersection) => intersection.validate(),
+        }
+    }
+}
+
+impl AddressInput {
+    /// Reject inputs with every component blank, malformed ZIP or postal codes, and
+    /// unknown state codes.
+    ///
+    /// US addresses need a 5 digit ZIP or ZIP+4 and Canadian addresses a postal code
+    /// such as `K1A 0B1`. States are checked against the US states and territories or
+    /// the Canadian provinces when the country is one of those or isn't given.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::{errors::ValidationError, request::address::AddressInput};
+    ///
+    /// let address = AddressInput {
+    ///     line_1: Some("1500 Sugar Bowl Dr".to_string()),
+    ///     line_2: None,
+    ///     city: Some("New Orleans".to_string()),
+    ///     state: Some("LA".to_string()),
+    ///     country: Some("US".to_string()),
+    ///     postal_code: Some("7011".to_string()),
+    /// };
+    /// assert_eq!(address.validate(), Err(ValidationError::PostalCode("7011".to_string())));
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let components = [&self.line_1, &self.line_2, &self.city, &self.state, &self.postal_code];
+        if components.iter().all(|component| non_blank(component).is_none()) {
+            return Err(ValidationError::Empty);
+        }
+        validate_region(&self.state, &self.postal_code, &self.country)
+    }
+}
+
+impl IntersectionInput {
+    /// Reject intersections missing either street, with the same state and postal code
+    /// checks as [`AddressInput::validate`].
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.street_1.trim().is_empty() || self.street_2.trim().is_empty() {
+            return Err(ValidationError::Empty);
+        }
+        validate_region(&self.state, &self.postal_code, &self.country)
+    }
+}
+
+fn validate_region(state: &Option<String>, postal_code: &Option<String>, country: &Option<String>) -> Result<(), ValidationError> {
+    let region = Region::of(country);
+    if let Some(state) = non_blank(state) {
+        let known = match region {
+            Region::UnitedStates => is_us_state(state),
+            Region::Canada => is_province(state),
+            Region::Unspecified => is_us_state(state) || is_province(state),
+            Region::Other => true,
+        };
+        if !known {
+            return Err(ValidationError::State(state.to_string()));
+        }
+    }
+    if let Some(postal_code) = non_blank(postal_code) {
+        let valid = match region {
+            Region::UnitedStates => is_zip(postal_code),
+            Region::Canada => is_canadian_postal_code(postal_code),
+            Region::Unspecified => is_zip(postal_code) || is_canadian_postal_code(postal_code),
+            Region::Other => postal_code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-'),
+        };
+        if !valid {
+            return Err(ValidationError::PostalCode(postal_code.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn is_us_state(state: &str) -> bool {
+    state.parse::<UsState>().is_ok()
+}
+
+fn is_province(state: &str) -> bool {
+    state.parse::<CaProvince>().is_ok()
+}
+
+/// `12345`, `12345-6789` or `123456789`.
+fn is_zip(postal_code: &str) -> bool {
+    let digits = |value: &str, len: usize| value.len() == len && value.bytes().all(|b| b.is_ascii_digit());
+    match postal_code.split_once('-') {
+        Some((zip, zip4)) => digits(zip, 5) && digits(zip4, 4),
+        None => digits(postal_code, 5) || digits(postal_code, 9),
+    }
+}
+
+/// `A1A 1A1`, with or without the space.
+fn is_canadian_postal_code(postal_code: &str) -> bool {
+    let compact = postal_code.replace(' ', "");
+    compact.len() == 6
+        && compact.chars().enumerate().all(|(i, c)| {
+            if i % 2 == 0 { c.is_ascii_alphabetic() } else { c.is_ascii_digit() }
+        })
+}
+
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
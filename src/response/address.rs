@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::request::address::Coordinates;
+use crate::request::{address::Coordinates, region::{CaProvince, Country, UsState}};
 
 use super::utils::Fields;
 
@@ -8,12 +9,20 @@ use super::utils::Fields;
 pub struct Address {
     pub query: Option<String>,
     pub address_components: AddressComponents,
+    /// Components of the second street when the result is an intersection.
+    pub address_components_secondary: Option<AddressComponents>,
     pub formatted_address: String,
     pub location: Coordinates,
     pub accuracy: f64,
     pub accuracy_type: String,
     pub source: String,
     pub fields: Option<Fields>,
+    /// Distance from the queried coordinates in kilometers, set on reverse geocoding results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -36,4 +45,33 @@ pub struct AddressComponents {
     #[serde(rename = "predirectional")]
     pub pre_directional: Option<String>,
     pub prefix: Option<String>,
-}
\ No newline at end of file
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Address {
+    /// Whether this result is an intersection of two streets.
+    pub fn is_intersection(&self) -> bool {
+        self.address_components_secondary.is_some()
+    }
+}
+
+impl AddressComponents {
+    /// The state as a [`UsState`], or `None` if it's missing or not a US state,
+    /// such as a Canadian province.
+    pub fn us_state(&self) -> Option<UsState> {
+        self.state.as_deref()?.parse().ok()
+    }
+
+    /// The province as a [`CaProvince`], or `None` if it's missing or not a Canadian
+    /// province or territory.
+    pub fn ca_province(&self) -> Option<CaProvince> {
+        self.state.as_deref()?.parse().ok()
+    }
+
+    /// The country as a [`Country`], or `None` if it's missing or not recognized.
+    pub fn country_code(&self) -> Option<Country> {
+        self.country.as_deref()?.parse().ok()
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::request::{address::Coordinates, coordinates::COORDINATE_PRECISION, distance::DistanceMode};
+
+use super::ResponseMeta;
+
+/// The result type for [`GeocodioProxy::distance`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistanceResponse {
+    pub origin: DistancePoint,
+    pub mode: DistanceMode,
+    pub destinations: Vec<DistanceDestination>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
+}
+
+/// The result type for [`GeocodioProxy::distance_matrix`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistanceMatrixResponse {
+    pub mode: DistanceMode,
+    pub results: Vec<DistanceMatrixResult>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
+}
+
+/// The distances from a single origin in a [`DistanceMatrixResponse`].
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistanceMatrixResult {
+    pub origin: DistancePoint,
+    pub destinations: Vec<DistanceDestination>,
+}
+
+/// An origin as it was resolved by the API.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistancePoint {
+    pub query: Option<String>,
+    /// `[latitude, longitude]`
+    pub location: Option<[f64; 2]>,
+    pub id: Option<String>,
+}
+
+/// A destination and its distance from the origin.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistanceDestination {
+    pub query: Option<String>,
+    /// `[latitude, longitude]`
+    pub location: Option<[f64; 2]>,
+    pub id: Option<String>,
+    pub distance_miles: f64,
+    pub distance_km: f64,
+    /// Only available when using [`DistanceMode::Driving`].
+    pub duration_seconds: Option<f64>,
+}
+
+impl DistancePoint {
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        self.location.map(|[latitude, longitude]| Coordinates { latitude, longitude })
+    }
+
+    fn from_coordinates(coordinates: &Coordinates) -> Self {
+        DistancePoint {
+            query: Some(coordinates.to_precision_string(COORDINATE_PRECISION)),
+            location: Some([coordinates.latitude, coordinates.longitude]),
+            id: None,
+        }
+    }
+}
+
+impl DistanceDestination {
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        self.location.map(|[latitude, longitude]| Coordinates { latitude, longitude })
+    }
+}
+
+impl DistanceResponse {
+    /// Calculate straight-line distances locally, without calling the API.
+    pub fn straight_line(origin: &Coordinates, destinations: &[Coordinates]) -> Self {
+        DistanceResponse {
+            origin: DistancePoint::from_coordinates(origin),
+            mode: DistanceMode::Straightline,
+            destinations: destinations.iter().map(|destination| {
+                let point = DistancePoint::from_coordinates(destination);
+                DistanceDestination {
+                    query: point.query,
+                    location: point.location,
+                    id: None,
+                    distance_miles: origin.distance_miles(destination),
+                    distance_km: origin.distance_km(destination),
+                    duration_seconds: None,
+                }
+            }).collect(),
+            meta: ResponseMeta::default(),
+        }
+    }
+}
+
+impl DistanceMatrixResponse {
+    /// Calculate a straight-line distance matrix locally, without calling the API.
+    pub fn straight_line(origins: &[Coordinates], destinations: &[Coordinates]) -> Self {
+        DistanceMatrixResponse {
+            mode: DistanceMode::Straightline,
+            results: origins.iter().map(|origin| {
+                let response = DistanceResponse::straight_line(origin, destinations);
+                DistanceMatrixResult { origin: response.origin, destinations: response.destinations }
+            }).collect(),
+            meta: ResponseMeta::default(),
+        }
+    }
+}
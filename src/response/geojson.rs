@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{address::Address, utils::Fields, GeocodeBatchResponse, GeocodeResponse, GeocodeReverseResponse, ResponseResult};
+
+/// A GeoJSON `FeatureCollection` of geocoded results.
+///
+/// Each result becomes a `Point` feature with `formatted_address`, `accuracy`,
+/// `accuracy_type`, `source` and `address_components` properties, plus any field
+/// appends picked with [`ToGeoJson::to_geojson_with_fields`].
+///
+/// ```rust
+/// use geocodio_lib_rust::{request::address::Coordinates, response::{address::Address, geojson::ToGeoJson, GeocodeResponse}};
+///
+/// let response = GeocodeResponse {
+///     results: vec![Address {
+///         formatted_address: "1500 Sugar Bowl Dr, New Orleans, LA 70112".to_string(),
+///         location: Coordinates { latitude: 29.950888, longitude: -90.081025 },
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+/// let geojson = serde_json::to_value(response.to_geojson()).unwrap();
+/// assert_eq!(geojson["type"], "FeatureCollection");
+/// assert_eq!(geojson["features"][0]["geometry"]["coordinates"][0], -90.081025);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+/// A GeoJSON `Feature` for a single result.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct Feature {
+    /// `None` when the result has no location.
+    pub geometry: Option<Point>,
+    pub properties: Map<String, Value>,
+}
+
+/// A GeoJSON `Point` geometry.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Point")]
+pub struct Point {
+    /// `[longitude, latitude]`, as required by GeoJSON.
+    pub coordinates: [f64; 2],
+}
+
+/// Conversion of geocoding responses into a GeoJSON [`FeatureCollection`].
+pub trait ToGeoJson {
+    /// Convert every result into a feature, picking the field appends named in
+    /// `fields` as extra properties. Fields are named as requested, e.g. `timezone` or
+    /// `cd`, or by their response key, see [`Fields::key`].
+    fn to_geojson_with_fields(&self, fields: &[&str]) -> FeatureCollection;
+
+    /// Convert every result into a feature, without field appends.
+    fn to_geojson(&self) -> FeatureCollection {
+        self.to_geojson_with_fields(&[])
+    }
+}
+
+impl ToGeoJson for GeocodeResponse {
+    fn to_geojson_with_fields(&self, fields: &[&str]) -> FeatureCollection {
+        FeatureCollection {
+            features: self.results.iter().map(|address| address_feature(address, fields)).collect(),
+        }
+    }
+}
+
+impl ToGeoJson for GeocodeReverseResponse {
+    fn to_geojson_with_fields(&self, fields: &[&str]) -> FeatureCollection {
+        FeatureCollection {
+            features: self
+                .results
+                .iter()
+                .flatten()
+                .map(|address| address_feature(address, fields))
+                .collect(),
+        }
+    }
+}
+
+/// Every result of every query in the batch, with the query as a `query` property.
+impl ToGeoJson for GeocodeBatchResponse {
+    fn to_geojson_with_fields(&self, fields: &[&str]) -> FeatureCollection {
+        let features = self
+            .results
+            .iter()
+            .flatten()
+            .flat_map(|batch| {
+                let results = batch
+                    .response
+                    .as_ref()
+                    .and_then(|response| response.results.as_ref())
+                    .map(|results| results.as_slice())
+                    .unwrap_or_default();
+                results.iter().map(move |result| {
+                    let mut feature = result_feature(result, fields);
+                    if let Some(query) = &batch.query {
+                        feature.properties.insert("query".to_string(), Value::String(query.clone()));
+                    }
+                    feature
+                })
+            })
+            .collect();
+        FeatureCollection { features }
+    }
+}
+
+fn address_feature(address: &Address, fields: &[&str]) -> Feature {
+    let mut properties = Map::new();
+    properties.insert("formatted_address".to_string(), address.formatted_address.clone().into());
+    properties.insert("accuracy".to_string(), address.accuracy.into());
+    properties.insert("accuracy_type".to_string(), address.accuracy_type.clone().into());
+    properties.insert("source".to_string(), address.source.clone().into());
+    properties.insert("address_components".to_string(), to_value(&address.address_components));
+    if let Some(secondary) = &address.address_components_secondary {
+        properties.insert("address_components_secondary".to_string(), to_value(secondary));
+    }
+    insert_fields(&mut properties, address.fields.as_ref(), fields);
+    Feature {
+        geometry: Some(Point { coordinates: [address.location.longitude, address.location.latitude] }),
+        properties,
+    }
+}
+
+fn result_feature(result: &ResponseResult, fields: &[&str]) -> Feature {
+    let mut properties = Map::new();
+    properties.insert("formatted_address".to_string(), to_value(&result.formatted_address));
+    properties.insert("accuracy".to_string(), to_value(&result.accuracy));
+    properties.insert("accuracy_type".to_string(), to_value(&result.accuracy_type));
+    properties.insert("source".to_string(), to_value(&result.source));
+    properties.insert("address_components".to_string(), to_value(&result.address_components));
+    if let Some(secondary) = &result.address_components_secondary {
+        properties.insert("address_components_secondary".to_string(), to_value(secondary));
+    }
+    insert_fields(&mut properties, result.fields.as_ref(), fields);
+    let geometry = result
+        .location
+        .as_ref()
+        .and_then(|location| Some(Point { coordinates: [location.lng?, location.lat?] }));
+    Feature { geometry, properties }
+}
+
+fn insert_fields(properties: &mut Map<String, Value>, appended: Option<&Fields>, fields: &[&str]) {
+    if fields.is_empty() {
+        return;
+    }
+    let Some(Value::Object(appended)) = appended.map(to_value) else {
+        return;
+    };
+    for field in fields {
+        if let Some(value) = appended.get(Fields::key(field)).filter(|value| !value.is_null()) {
+            properties.insert(field.to_string(), value.clone());
+        }
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
@@ -1,13 +1,21 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::response::{address::{Address, AddressComponents}, utils::{Input, Location}};
+use crate::{errors::ValidationError, request::address::Coordinates, response::{address::{Address, AddressComponents}, utils::{Fields, Input, Location}, warning::Warning}};
 
 /// Address information formatted to match the json response
 pub mod address;
 /// Congressional district information
 pub mod congressional;
+/// Distances between origins and destinations
+pub mod distance;
+/// GeoJSON export of geocoding results
+pub mod geojson;
 /// Extra response information
 pub mod utils;
+mod reverse;
+/// Warnings returned by the API alongside results
+pub mod warning;
 
 /// The result type for [`GeocodioProxy::geocode`]. 
 /// 
@@ -19,17 +27,124 @@ pub mod utils;
 pub struct GeocodeResponse {
     pub input: Input,
     pub results: Vec<Address>,
+    /// See [`Warning`].
+    #[serde(rename = "_warnings", default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
 }
 
 /// The result type for [`GeocodioProxy::geocode_batch`] and [`GeocodioProxy::reverse_geocode_batch`].
 #[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GeocodeBatchResponse {
     pub results: Option<Vec<BatchResult>>,
+    /// See [`Warning`].
+    #[serde(rename = "_warnings", default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    /// Addresses that failed validation and weren't sent.
+    #[serde(skip)]
+    pub rejected: Vec<BatchRejection>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GeocodeReverseResponse {
     pub results: Option<Vec<Address>>,
+    /// See [`Warning`].
+    #[serde(rename = "_warnings", default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
+}
+
+/// Information about how a response was obtained that isn't part of the API's json.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// The label of the API key that served the call, see [`KeyPool`](crate::request::keys::KeyPool).
+    /// `None` when a [`Middleware`](crate::middleware::Middleware) answered instead.
+    pub served_by: Option<String>,
+    /// The json the response was decoded from, kept when
+    /// [`GeocodeOptions::keep_raw`](crate::request::options::GeocodeOptions::keep_raw) is set.
+    pub raw: Option<Value>,
+    /// Values that had to be changed to decode the response, when
+    /// [`GeocodeOptions::lenient`](crate::request::options::GeocodeOptions::lenient) is set.
+    pub decode_warnings: Vec<DecodeWarning>,
+}
+
+/// A value of the wrong type, or a missing field, that was tolerated by lenient decoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeWarning {
+    /// Where the value is in the json, e.g. `results[0].accuracy_type`.
+    pub path: String,
+    /// What was wrong with it, e.g. "invalid type: null, expected a string".
+    pub message: String,
+}
+
+pub(crate) trait WithMeta {
+    fn meta_mut(&mut self) -> &mut ResponseMeta;
+
+    /// The warnings the API returned anywhere in the response.
+    fn api_warnings(&self) -> Vec<&Warning> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_with_meta {
+    ($($res:ty),*) => {
+        $(impl WithMeta for $res {
+            fn meta_mut(&mut self) -> &mut ResponseMeta {
+                &mut self.meta
+            }
+        })*
+    };
+}
+
+impl_with_meta!(
+    GeocodeSimpleResponse,
+    distance::DistanceResponse,
+    distance::DistanceMatrixResponse
+);
+
+impl WithMeta for GeocodeResponse {
+    fn meta_mut(&mut self) -> &mut ResponseMeta {
+        &mut self.meta
+    }
+
+    fn api_warnings(&self) -> Vec<&Warning> {
+        self.warnings.iter().collect()
+    }
+}
+
+impl WithMeta for GeocodeReverseResponse {
+    fn meta_mut(&mut self) -> &mut ResponseMeta {
+        &mut self.meta
+    }
+
+    fn api_warnings(&self) -> Vec<&Warning> {
+        self.warnings.iter().collect()
+    }
+}
+
+impl WithMeta for GeocodeBatchResponse {
+    fn meta_mut(&mut self) -> &mut ResponseMeta {
+        &mut self.meta
+    }
+
+    /// The warnings of the whole batch followed by those of each query.
+    fn api_warnings(&self) -> Vec<&Warning> {
+        let queries = self.results.iter().flatten().filter_map(|result| result.response.as_ref());
+        self.warnings.iter().chain(queries.flat_map(|response| &response.warnings)).collect()
+    }
 }
 
 /// Individual results from each [`GeocodeBatchResponse`].
@@ -37,20 +152,72 @@ pub struct GeocodeReverseResponse {
 pub struct BatchResult {
     pub query: Option<String>,
     pub response: Option<Response>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// An address in a batch that wasn't sent because it failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRejection {
+    /// Position of the address in the batch.
+    pub index: usize,
+    pub query: String,
+    pub error: ValidationError,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Response {
     pub input: Option<Input>,
     pub results: Option<Vec<ResponseResult>>,
+    /// See [`Warning`].
+    #[serde(rename = "_warnings", default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ResponseResult {
     pub address_components: Option<AddressComponents>,
+    pub address_components_secondary: Option<AddressComponents>,
     pub formatted_address: Option<String>,
     pub location: Option<Location>,
     pub accuracy: Option<f64>,
     pub accuracy_type: Option<String>,
     pub source: Option<String>,
+    pub fields: Option<Fields>,
+    /// Distance from the queried coordinates in kilometers, set on reverse geocoding results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The result type for [`GeocodioProxy::geocode_simple`] and [`GeocodioProxy::reverse_geocode_simple`].
+///
+/// A compact response returned when requesting `format=simple`, containing only the
+/// best match for the query.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeocodeSimpleResponse {
+    pub address: String,
+    pub lat: f64,
+    pub lng: f64,
+    pub accuracy: f64,
+    pub accuracy_type: String,
+    pub source: String,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip)]
+    pub meta: ResponseMeta,
+}
+
+impl GeocodeSimpleResponse {
+    /// The location of the result as [`Coordinates`].
+    pub fn location(&self) -> Coordinates {
+        Coordinates { latitude: self.lat, longitude: self.lng }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use crate::request::{address::Coordinates, options::GeocodeOptions};
+
+use super::{address::Address, GeocodeBatchResponse, GeocodeReverseResponse, ResponseResult};
+
+/// Accuracy types kept by [`GeocodeOptions::rooftop_only`].
+const ROOFTOP_ACCURACY_TYPES: [&str; 2] = ["rooftop", "point"];
+
+/// A reverse geocoding result that can be ranked by distance.
+trait Ranked {
+    fn distance_km(&self) -> Option<f64>;
+    fn accuracy_type(&self) -> Option<&str>;
+}
+
+impl Ranked for Address {
+    fn distance_km(&self) -> Option<f64> {
+        self.distance_km
+    }
+
+    fn accuracy_type(&self) -> Option<&str> {
+        Some(&self.accuracy_type)
+    }
+}
+
+impl Ranked for ResponseResult {
+    fn distance_km(&self) -> Option<f64> {
+        self.distance_km
+    }
+
+    fn accuracy_type(&self) -> Option<&str> {
+        self.accuracy_type.as_deref()
+    }
+}
+
+impl GeocodeReverseResponse {
+    /// Set the distance of each result from `query`, then sort and filter them.
+    pub(crate) fn rank(&mut self, query: &Coordinates, options: &GeocodeOptions) {
+        let Some(results) = &mut self.results else {
+            return;
+        };
+        for result in results.iter_mut() {
+            result.distance_km = Some(query.distance_km(&result.location));
+        }
+        rank(results, options);
+    }
+}
+
+impl GeocodeBatchResponse {
+    /// Set the distance of each result from its query, given in the order they were
+    /// sent, then sort and filter the results of each query.
+    pub(crate) fn rank(&mut self, queries: &[Coordinates], options: &GeocodeOptions) {
+        let batch = self.results.iter_mut().flatten().zip(queries);
+        for (batch_result, query) in batch {
+            let Some(results) = batch_result.response.as_mut().and_then(|response| response.results.as_mut()) else {
+                continue;
+            };
+            for result in results.iter_mut() {
+                let location = result.location.as_ref().and_then(|location| Some(Coordinates { latitude: location.lat?, longitude: location.lng? }));
+                result.distance_km = location.map(|location| query.distance_km(&location));
+            }
+            rank(results, options);
+        }
+    }
+}
+
+/// Sort `results` nearest first, keeping the API's order for ties and putting results
+/// without a location last, then apply the filters and limit of `options`.
+fn rank<T: Ranked>(results: &mut Vec<T>, options: &GeocodeOptions) {
+    results.sort_by(|a, b| {
+        let a = a.distance_km().unwrap_or(f64::INFINITY);
+        let b = b.distance_km().unwrap_or(f64::INFINITY);
+        a.total_cmp(&b)
+    });
+    if let Some(max_distance_km) = options.max_distance_km {
+        results.retain(|result| result.distance_km().is_some_and(|distance| distance <= max_distance_km));
+    }
+    if options.rooftop_only {
+        results.retain(|result| result.accuracy_type().is_some_and(|accuracy_type| ROOFTOP_ACCURACY_TYPES.contains(&accuracy_type)));
+    }
+    if let Some(limit) = options.limit {
+        results.truncate(limit as usize);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use super::{address::AddressComponents, congressional::{CongressionalDistrict, StateLegislativeDistricts}};
 
@@ -13,6 +14,38 @@ pub struct Fields {
     pub school_districts: Option<SchoolDistricts>,
     pub census: Option<CensusResults>,
     pub acs: Option<CencusAcs>,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Fields {
+    /// The key a field append is returned under, for a name as requested in
+    /// [`GeocodeOptions::fields`](crate::request::options::GeocodeOptions::fields).
+    /// Names that are already response keys are returned unchanged.
+    ///
+    /// ```rust
+    /// use geocodio_lib_rust::response::utils::Fields;
+    ///
+    /// assert_eq!(Fields::key("cd118"), "congressional_districts");
+    /// assert_eq!(Fields::key("provriding"), "provincial_riding");
+    /// assert_eq!(Fields::key("timezone"), "timezone");
+    /// assert_eq!(Fields::key("congressional_districts"), "congressional_districts");
+    /// ```
+    pub fn key(field: &str) -> &str {
+        let numbered = |prefix: &str| field.strip_prefix(prefix).is_some_and(|rest| rest.bytes().all(|b| b.is_ascii_digit()));
+        match field {
+            "school" => "school_districts",
+            "stateleg" => "state_legislative_districts",
+            "stateleg-next" => "state_legislative_districts_next",
+            "provriding" => "provincial_riding",
+            "provriding-next" => "provincial_riding_next",
+            _ if numbered("cd") => "congressional_districts",
+            _ if numbered("census") => "census",
+            _ if field.starts_with("acs-") => "acs",
+            _ => field,
+        }
+    }
 }
 
 // TO DO
@@ -78,7 +111,11 @@ pub struct FacilityCode {
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Input {
     pub address_components: AddressComponents,
+    pub address_components_secondary: Option<AddressComponents>,
     pub formatted_address: String,
+    /// Fields returned by the API that this version of the crate doesn't know about.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// What a [`Warning`] is about, guessed from its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// A field append or parameter that has been renamed or will be removed, e.g.
+    /// "Field 'cd113' is deprecated, use 'cd' instead".
+    Deprecated,
+    /// The query only partially matched an address.
+    PartialMatch,
+    /// A field append that doesn't exist or isn't available for the results.
+    UnsupportedField,
+    /// Any other warning.
+    Other,
+}
+
+impl WarningKind {
+    /// A short, stable name for the kind of warning, used to label logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarningKind::Deprecated => "deprecated",
+            WarningKind::PartialMatch => "partial_match",
+            WarningKind::UnsupportedField => "unsupported_field",
+            WarningKind::Other => "other",
+        }
+    }
+}
+
+/// A warning the API returned in the `_warnings` list of a response, e.g. about a
+/// deprecated field append. Responses without warnings have an empty list.
+///
+/// The API only sends the message, so the kind and the field it's about are worked out
+/// from its wording. It serializes back to the message.
+///
+/// ```rust
+/// use geocodio_lib_rust::response::warning::{Warning, WarningKind};
+///
+/// let warning = Warning::from("Field 'cd113' is deprecated, use 'cd' instead");
+/// assert_eq!(warning.kind, WarningKind::Deprecated);
+/// assert_eq!(warning.field.as_deref(), Some("cd113"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Warning {
+    pub kind: WarningKind,
+    /// The first quoted name in the message, usually the field the warning is about.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl From<String> for Warning {
+    fn from(message: String) -> Self {
+        let lower = message.to_lowercase();
+        let kind = if lower.contains("deprecat") {
+            WarningKind::Deprecated
+        } else if lower.contains("partial") {
+            WarningKind::PartialMatch
+        } else if ["not supported", "unsupported", "not available", "unknown field", "invalid field", "not a valid field"]
+            .iter()
+            .any(|phrase| lower.contains(phrase))
+        {
+            WarningKind::UnsupportedField
+        } else {
+            WarningKind::Other
+        };
+        let field = quoted(&message);
+        Warning { kind, field, message }
+    }
+}
+
+impl From<&str> for Warning {
+    fn from(message: &str) -> Self {
+        Warning::from(message.to_string())
+    }
+}
+
+impl From<Warning> for String {
+    fn from(warning: Warning) -> Self {
+        warning.message
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The first non-empty text between a pair of quotes or backticks.
+fn quoted(message: &str) -> Option<String> {
+    let (start, quote) = message.char_indices().find(|(_, c)| matches!(c, '\'' | '"' | '`'))?;
+    let rest = &message[start + 1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string()).filter(|field| !field.is_empty())
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use crate::{errors::Error, response::{warning::Warning, DecodeWarning}};
+
+/// Record a response from the API and how long it took.
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_response(endpoint: &str, key: &str, status: u16, elapsed: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(endpoint, key, status, latency_ms = elapsed.as_millis() as u64, "geocodio response");
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!("geocodio_requests_total", "endpoint" => endpoint.to_string(), "status" => status.to_string()).increment(1);
+        metrics::histogram!("geocodio_request_duration_seconds", "endpoint" => endpoint.to_string()).record(elapsed.as_secs_f64());
+    }
+}
+
+/// Record a key that was rejected before trying the next one in the pool.
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_retry(endpoint: &str, key: &str, status: u16) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(endpoint, key, status, "key rejected, retrying with the next key");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("geocodio_retries_total", "endpoint" => endpoint.to_string(), "status" => status.to_string()).increment(1);
+}
+
+/// Record the lookups billed for a successful call.
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_lookups(endpoint: &str, lookups: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("lookups", lookups);
+    #[cfg(feature = "metrics")]
+    metrics::counter!("geocodio_lookups_total", "endpoint" => endpoint.to_string()).increment(lookups);
+}
+
+/// Record a failed call by [`Error::kind`].
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_error(endpoint: &str, error: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(endpoint, kind = error.kind(), %error, "geocodio call failed");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("geocodio_errors_total", "endpoint" => endpoint.to_string(), "kind" => error.kind()).increment(1);
+}
+
+/// Record a value that lenient decoding had to change.
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_decode_warning(endpoint: &str, warning: &DecodeWarning) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(endpoint, path = %warning.path, message = %warning.message, "tolerated a value that didn't decode");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("geocodio_decode_warnings_total", "endpoint" => endpoint.to_string()).increment(1);
+}
+
+/// Record a warning the API returned, such as a deprecated field append.
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_api_warning(endpoint: &str, warning: &Warning) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(endpoint, kind = warning.kind.as_str(), field = warning.field.as_deref(), message = %warning.message, "geocodio api warning");
+    #[cfg(feature = "metrics")]
+    metrics::counter!("geocodio_api_warnings_total", "endpoint" => endpoint.to_string(), "kind" => warning.kind.as_str()).increment(1);
+}
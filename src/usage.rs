@@ -0,0 +1,204 @@
+use std::{collections::BTreeMap, sync::Mutex, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::{errors::Error, request::options::GeocodeOptions};
+
+/// A calendar month, in UTC, that lookups are counted against.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BillingPeriod {
+    pub year: i64,
+    pub month: u32,
+}
+
+impl BillingPeriod {
+    /// The current month in UTC.
+    pub fn current() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or_default();
+        Self::from_days(seconds as i64 / 86_400)
+    }
+
+    /// Convert days since the unix epoch into a year and month.
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        BillingPeriod { year, month: month as u32 }
+    }
+}
+
+/// Lookups used by a single endpoint.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct EndpointUsage {
+    /// Number of successful requests.
+    pub calls: u64,
+    /// Number of addresses, coordinates or destinations in those requests.
+    pub items: u64,
+    /// Billable lookups, where each field append counts as an extra lookup per item.
+    pub lookups: u64,
+}
+
+/// A snapshot of the lookups used by a [`GeocodioProxy`](crate::GeocodioProxy), see
+/// [`GeocodioProxy::usage`](crate::GeocodioProxy::usage).
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub period: BillingPeriod,
+    /// Usage per endpoint, e.g. `geocode` or `reverse`.
+    pub endpoints: BTreeMap<String, EndpointUsage>,
+    /// The monthly lookup limit, if one is set.
+    pub budget: Option<u64>,
+    /// Lookups of calls that are still waiting for the API. They count against the
+    /// budget until the call finishes, so concurrent calls can't overshoot it together.
+    pub in_flight: u64,
+    /// The estimated cost of the lookups in this period with the configured [`Pricing`].
+    pub estimated_cost: f64,
+}
+
+impl Usage {
+    pub fn total_lookups(&self) -> u64 {
+        self.endpoints.values().map(|usage| usage.lookups).sum()
+    }
+
+    /// Lookups left before the budget is reached.
+    pub fn remaining(&self) -> Option<u64> {
+        self.budget.map(|budget| budget.saturating_sub(self.total_lookups() + self.in_flight))
+    }
+}
+
+/// A range of monthly lookups charged at the same price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTier {
+    /// The last lookup, counted from the start of the month, included in this tier.
+    /// `None` means every lookup after the previous tier.
+    pub up_to: Option<u64>,
+    pub price_per_thousand: f64,
+}
+
+/// Pricing tiers used to estimate the cost of lookups.
+///
+/// The default has no tiers, so every estimate is zero until the tiers of your plan
+/// are set.
+///
+/// ```rust
+/// use geocodio_lib_rust::usage::{PriceTier, Pricing};
+///
+/// let pricing = Pricing {
+///     tiers: vec![
+///         PriceTier { up_to: Some(10_000), price_per_thousand: 0.0 },
+///         PriceTier { up_to: None, price_per_thousand: 0.5 },
+///     ],
+/// };
+/// assert_eq!(pricing.estimate(10_000), 0.0);
+/// assert_eq!(pricing.estimate(20_000), 5.0);
+/// assert_eq!(Pricing::default().estimate(20_000), 0.0);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Pricing {
+    pub tiers: Vec<PriceTier>,
+}
+
+impl Pricing {
+    /// The estimated cost of `lookups` made in the same month.
+    pub fn estimate(&self, lookups: u64) -> f64 {
+        let mut cost = 0.0;
+        let mut start = 0;
+        for tier in &self.tiers {
+            let end = tier.up_to.unwrap_or(u64::MAX).min(lookups);
+            if end > start {
+                cost += (end - start) as f64 * tier.price_per_thousand / 1000.0;
+                start = end;
+            }
+            if start >= lookups {
+                break;
+            }
+        }
+        cost
+    }
+}
+
+/// Lookups held against the budget while a call is in flight. Dropping it without
+/// calling [`Reservation::record`], e.g. when the call fails, releases them.
+pub(crate) struct Reservation<'a> {
+    tracker: &'a UsageTracker,
+    lookups: u64,
+}
+
+impl Reservation<'_> {
+    /// Count the reserved lookups as used by a successful call to `endpoint`.
+    pub fn record(mut self, endpoint: &str, items: usize) {
+        let mut usage = self.tracker.lock();
+        usage.in_flight = usage.in_flight.saturating_sub(self.lookups);
+        let endpoint = usage.endpoints.entry(endpoint.to_string()).or_default();
+        endpoint.calls += 1;
+        endpoint.items += items as u64;
+        endpoint.lookups += self.lookups;
+        self.lookups = 0;
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.lookups > 0 {
+            let mut usage = self.tracker.lock();
+            usage.in_flight = usage.in_flight.saturating_sub(self.lookups);
+        }
+    }
+}
+
+/// Counts the lookups made by a [`GeocodioProxy`](crate::GeocodioProxy) and enforces its budget.
+#[derive(Debug, Default)]
+pub(crate) struct UsageTracker {
+    usage: Mutex<Usage>,
+    pub pricing: Pricing,
+}
+
+impl UsageTracker {
+    /// Billable lookups for `items` queries with the field appends in `options`.
+    pub fn lookups(items: usize, options: &GeocodeOptions) -> u64 {
+        items as u64 * (1 + options.fields.len() as u64)
+    }
+
+    pub fn set_budget(&self, budget: Option<u64>) {
+        self.lock().budget = budget;
+    }
+
+    /// Hold `lookups` against the budget for a call, or refuse it if they would go over.
+    /// Checking and holding happen under one lock, so concurrent calls see each other.
+    pub fn reserve(&self, lookups: u64) -> Result<Reservation<'_>, Error> {
+        let mut usage = self.lock();
+        let used = usage.total_lookups() + usage.in_flight;
+        match usage.budget {
+            Some(limit) if used + lookups > limit => Err(Error::BudgetExceeded { limit, used, requested: lookups }),
+            _ => {
+                usage.in_flight += lookups;
+                Ok(Reservation { tracker: self, lookups })
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> Usage {
+        let mut usage = self.lock().clone();
+        usage.estimated_cost = self.pricing.estimate(usage.total_lookups());
+        usage
+    }
+
+    pub fn reset(&self) {
+        let mut usage = self.lock();
+        usage.endpoints.clear();
+        usage.period = BillingPeriod::current();
+    }
+
+    /// Lock the usage, starting over when a new month begins.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Usage> {
+        let mut usage = self.usage.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let period = BillingPeriod::current();
+        if usage.period != period {
+            usage.endpoints.clear();
+            usage.period = period;
+        }
+        usage
+    }
+}